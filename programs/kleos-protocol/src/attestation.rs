@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    hash::hash,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+use crate::{constants::MAX_MULTIPLIER, errors::ProtocolError, AttestationReceipt};
+
+/// Message signed by the scoring authority:
+/// market (32) | user (32) | item (1) | raw_stake (u64 LE) | effective_stake (u128 LE) | expiry_ts (i64 LE).
+pub const ATTESTATION_MESSAGE_LEN: usize = 32 + 32 + 1 + 8 + 16 + 8;
+
+/// Ed25519 program data header: signature count (u8), padding (u8), then one offsets record
+/// of seven u16 fields.
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+
+/// Instruction index meaning "this instruction" in Ed25519 offsets.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Scoring-authority approval for a single position, verified by the Ed25519 program.
+pub struct Attestation {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub selected_item_index: u8,
+    pub raw_stake: u64,
+    pub effective_stake: u128,
+    pub expiry_ts: i64,
+    /// Hash of the signed message; seeds the receipt that makes the attestation single-use.
+    pub digest: [u8; 32],
+}
+
+impl Attestation {
+    /// Load the attestation signed by `scoring_authority` from the Ed25519 program instruction
+    /// immediately preceding the current one. The runtime has already checked the signature;
+    /// this checks who signed and what.
+    pub fn load(instructions: &AccountInfo, scoring_authority: &Pubkey) -> Result<Self> {
+        require!(
+            *scoring_authority != Pubkey::default(),
+            ProtocolError::MissingAttestation
+        );
+
+        let current_index = load_current_index_checked(instructions)?;
+        require!(current_index > 0, ProtocolError::MissingAttestation);
+
+        let ix = load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+        require_keys_eq!(
+            ix.program_id,
+            ed25519_program::ID,
+            ProtocolError::MissingAttestation
+        );
+
+        let data = &ix.data;
+        require!(
+            data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+            ProtocolError::InvalidAttestation
+        );
+
+        let offsets = &data[ED25519_HEADER_LEN..ED25519_HEADER_LEN + ED25519_OFFSETS_LEN];
+        let read_u16 = |i: usize| u16::from_le_bytes([offsets[i * 2], offsets[i * 2 + 1]]);
+
+        let signature_instruction_index = read_u16(1);
+        let public_key_offset = read_u16(2) as usize;
+        let public_key_instruction_index = read_u16(3);
+        let message_data_offset = read_u16(4) as usize;
+        let message_data_size = read_u16(5) as usize;
+        let message_instruction_index = read_u16(6);
+
+        // Signature, key and message must all live in the Ed25519 instruction itself
+        require!(
+            signature_instruction_index == CURRENT_INSTRUCTION
+                && public_key_instruction_index == CURRENT_INSTRUCTION
+                && message_instruction_index == CURRENT_INSTRUCTION,
+            ProtocolError::InvalidAttestation
+        );
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + ED25519_PUBKEY_LEN)
+            .ok_or(ProtocolError::InvalidAttestation)?;
+        require!(
+            public_key == scoring_authority.as_ref(),
+            ProtocolError::InvalidAttestation
+        );
+
+        require!(
+            message_data_size == ATTESTATION_MESSAGE_LEN,
+            ProtocolError::InvalidAttestation
+        );
+        let message = data
+            .get(message_data_offset..message_data_offset + message_data_size)
+            .ok_or(ProtocolError::InvalidAttestation)?;
+
+        Ok(Self::parse(message))
+    }
+
+    fn parse(message: &[u8]) -> Self {
+        let mut market = [0u8; 32];
+        let mut user = [0u8; 32];
+        let mut raw_stake = [0u8; 8];
+        let mut effective_stake = [0u8; 16];
+        let mut expiry_ts = [0u8; 8];
+
+        market.copy_from_slice(&message[0..32]);
+        user.copy_from_slice(&message[32..64]);
+        raw_stake.copy_from_slice(&message[65..73]);
+        effective_stake.copy_from_slice(&message[73..89]);
+        expiry_ts.copy_from_slice(&message[89..97]);
+
+        Self {
+            market: Pubkey::new_from_array(market),
+            user: Pubkey::new_from_array(user),
+            selected_item_index: message[64],
+            raw_stake: u64::from_le_bytes(raw_stake),
+            effective_stake: u128::from_le_bytes(effective_stake),
+            expiry_ts: i64::from_le_bytes(expiry_ts),
+            digest: hash(message).to_bytes(),
+        }
+    }
+
+    /// Check the attestation covers this exact position and is still valid; returns the
+    /// attested effective stake.
+    pub fn verify(
+        &self,
+        market: &Pubkey,
+        user: &Pubkey,
+        selected_item_index: u8,
+        raw_stake: u64,
+        now: i64,
+    ) -> Result<u128> {
+        require!(
+            self.market == *market
+                && self.user == *user
+                && self.selected_item_index == selected_item_index
+                && self.raw_stake == raw_stake,
+            ProtocolError::InvalidAttestation
+        );
+
+        require!(now <= self.expiry_ts, ProtocolError::AttestationExpired);
+
+        require!(
+            self.effective_stake <= raw_stake as u128 * MAX_MULTIPLIER,
+            ProtocolError::EffectiveStakeTooLarge
+        );
+
+        Ok(self.effective_stake)
+    }
+
+    /// Create the receipt PDA `[b"attestation", digest]` so the same signed message can never
+    /// be used again. Lamports sent to the address beforehand do not block it: like Anchor's
+    /// `init`, a funded address is topped up, allocated and assigned instead of created.
+    pub fn consume<'info>(
+        &self,
+        receipt: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let (expected, bump) =
+            Pubkey::find_program_address(&[b"attestation", self.digest.as_ref()], &crate::ID);
+
+        require_keys_eq!(receipt.key(), expected, ProtocolError::InvalidAttestation);
+        require!(
+            *receipt.owner != crate::ID,
+            ProtocolError::AttestationAlreadyUsed
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"attestation", self.digest.as_ref(), &[bump]]];
+        let space = 8 + AttestationReceipt::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+        let current_lamports = receipt.lamports();
+
+        if current_lamports == 0 {
+            create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    CreateAccount {
+                        from: payer.clone(),
+                        to: receipt.clone(),
+                    },
+                    signer_seeds,
+                ),
+                rent,
+                space as u64,
+                &crate::ID,
+            )?;
+        } else {
+            let top_up = rent.saturating_sub(current_lamports);
+            if top_up > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: receipt.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+
+            allocate(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Allocate {
+                        account_to_allocate: receipt.clone(),
+                    },
+                    signer_seeds,
+                ),
+                space as u64,
+            )?;
+            assign(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Assign {
+                        account_to_assign: receipt.clone(),
+                    },
+                    signer_seeds,
+                ),
+                &crate::ID,
+            )?;
+        }
+
+        let record = AttestationReceipt {
+            payer: payer.key(),
+            expiry_ts: self.expiry_ts,
+            bump,
+        };
+        let mut data = receipt.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])
+    }
+}
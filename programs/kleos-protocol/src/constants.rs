@@ -1,3 +1,18 @@
 pub const BPS_DENOMINATOR: u64 = 10_000;
 pub const MAX_MULTIPLIER: u128 = 20;
-pub const MAX_ITEMS: usize = 10;
\ No newline at end of file
+pub const MAX_ITEMS: usize = 10;
+pub const MAX_ITEM_LABEL_LEN: usize = 64;
+/// Default time winners have to claim after settlement before the vault can be swept.
+pub const DEFAULT_CLAIM_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+pub const MAX_CLAIM_WINDOW_SECS: i64 = 365 * 24 * 60 * 60;
+/// `Protocol.pause_flags` bits. Each one halts a single class of operation.
+pub const PAUSE_MARKET_CREATION: u8 = 1 << 0;
+pub const PAUSE_DEPOSITS: u8 = 1 << 1;
+pub const PAUSE_SETTLEMENT: u8 = 1 << 2;
+pub const PAUSE_CLAIMS: u8 = 1 << 3;
+pub const PAUSE_ALL: u8 = PAUSE_MARKET_CREATION | PAUSE_DEPOSITS | PAUSE_SETTLEMENT | PAUSE_CLAIMS;
+/// Default wait after a market's `end_ts` before unsettled positions can be withdrawn.
+pub const DEFAULT_EMERGENCY_TIMEOUT_SECS: i64 = 30 * 24 * 60 * 60;
+pub const MAX_EMERGENCY_TIMEOUT_SECS: i64 = 365 * 24 * 60 * 60;
+/// Upper bound on the protocol timelock so a queued delay change cannot lock parameters forever.
+pub const MAX_TIMELOCK_DELAY_SECS: i64 = 30 * 24 * 60 * 60;
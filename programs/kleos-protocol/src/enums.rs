@@ -1,9 +1,30 @@
 use anchor_lang::prelude::*;
 
+/// Stored by variant index, so new variants go at the end to keep existing accounts decodable.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
 pub enum MarketStatus {
     Draft,
     Open,
     Closed,
     Settled,
+    Resolved,
+    Cancelled,
+}
+
+/// Delegated protocol roles. The admin authority is the super-admin that grants them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Pauser,
+    MarketOperator,
+    FeeManager,
+}
+
+/// Groups of fields in a `PendingProtocolUpdate`, each timed and cancelled on its own.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProtocolUpdateGroup {
+    /// Protocol fee, creator fee cap and referral fee; owned by the fee manager.
+    Fees,
+    /// Treasury, scoring authority, timelock, emergency timeout and claim window; owned by the
+    /// admin.
+    Admin,
 }
@@ -35,4 +35,154 @@ pub enum ProtocolError {
 
     #[msg("Math overflow occurred.")]
     MathOverflow,
+
+    #[msg("Market has not been resolved.")]
+    MarketNotResolved,
+
+    #[msg("Position already refunded.")]
+    AlreadyRefunded,
+
+    #[msg("Market still has unclaimed positions.")]
+    UnclaimedPositions,
+
+    #[msg("Claim deadline has passed.")]
+    ClaimDeadlinePassed,
+
+    #[msg("Claim deadline has not passed yet.")]
+    ClaimDeadlineNotReached,
+
+    #[msg("Fee destination does not match the configured treasury.")]
+    InvalidTreasury,
+
+    #[msg("Market fee can only be lowered.")]
+    FeeIncreaseNotAllowed,
+
+    #[msg("Item label count does not match item_count.")]
+    ItemCountMismatch,
+
+    #[msg("Item label is empty or too long.")]
+    InvalidItemLabel,
+
+    #[msg("Item labels do not hash to items_hash.")]
+    ItemsHashMismatch,
+
+    #[msg("Multiplier schedule must stay between 1x and the maximum and must not increase.")]
+    InvalidMultiplierSchedule,
+
+    #[msg("Effective stake is below the accepted minimum.")]
+    EffectiveStakeBelowMinimum,
+
+    #[msg("Market requires a scoring attestation.")]
+    MissingAttestation,
+
+    #[msg("Scoring attestation is malformed or does not match this position.")]
+    InvalidAttestation,
+
+    #[msg("Scoring attestation has expired.")]
+    AttestationExpired,
+
+    #[msg("Scoring attestation has already been used.")]
+    AttestationAlreadyUsed,
+
+    #[msg("Payout would exceed the distributable pool.")]
+    PayoutExceedsPool,
+
+    #[msg("Stake mint uses a Token-2022 extension that is not supported.")]
+    UnsupportedMintExtension,
+
+    #[msg("Stake mint is not enabled for markets.")]
+    MintNotAllowed,
+
+    #[msg("Mint config stake bounds are invalid.")]
+    InvalidMintConfig,
+
+    #[msg("Stake is below the minimum for this mint.")]
+    StakeBelowMinimum,
+
+    #[msg("Stake is above the maximum for this mint.")]
+    StakeAboveMaximum,
+
+    #[msg("No admin transfer is pending.")]
+    NoPendingAdmin,
+
+    #[msg("Role is not assigned.")]
+    RoleNotAssigned,
+
+    #[msg("Timelock delay is out of range.")]
+    InvalidTimelockDelay,
+
+    #[msg("No protocol update is queued.")]
+    NoPendingUpdate,
+
+    #[msg("Queued protocol update is not yet executable.")]
+    TimelockNotElapsed,
+
+    #[msg("Pause flags contain unknown bits.")]
+    InvalidPauseFlags,
+
+    #[msg("Market is frozen.")]
+    MarketFrozen,
+
+    #[msg("Market is not frozen.")]
+    MarketNotFrozen,
+
+    #[msg("Emergency timeout is out of range.")]
+    InvalidEmergencyTimeout,
+
+    #[msg("Emergency withdrawal is not available for this market yet.")]
+    EmergencyWithdrawalNotAvailable,
+
+    #[msg("Market has positions that have not been refunded.")]
+    MarketNotEmpty,
+
+    #[msg("Only the market operator can create markets in this mint.")]
+    PermissionlessCreationDisabled,
+
+    #[msg("Creator bond must be released or slashed first.")]
+    CreatorBondOutstanding,
+
+    #[msg("Creator bond cannot be released in the current market state.")]
+    CreatorBondNotReleasable,
+
+    #[msg("Market has no creator bond.")]
+    NoCreatorBond,
+
+    #[msg("Creator token account is required to post the bond.")]
+    MissingCreatorTokenAccount,
+
+    #[msg("Creator fee exceeds the protocol maximum.")]
+    InvalidCreatorFeeBps,
+
+    #[msg("Referral fee must be at most 10000 bps.")]
+    InvalidReferralFeeBps,
+
+    #[msg("Referrer account does not match the position or mint.")]
+    InvalidReferrer,
+
+    #[msg("Referrer account is required for a referred position.")]
+    MissingReferrerAccount,
+
+    #[msg("No referral rewards to withdraw.")]
+    NoReferralRewards,
+
+    #[msg("Protocol update must change at least one parameter.")]
+    EmptyProtocolUpdate,
+
+    #[msg("Claim window is out of range.")]
+    InvalidClaimWindow,
+
+    #[msg("Creator bond can only be slashed on a cancelled market before it is releasable.")]
+    CreatorBondNotSlashable,
+
+    #[msg("Position can still be claimed or refunded.")]
+    PositionNotClosable,
+
+    #[msg("Market has position accounts that must be closed first.")]
+    OpenPositions,
+
+    #[msg("Scoring attestation has not expired yet.")]
+    AttestationNotExpired,
+
+    #[msg("Market has referral accounts that must be collected first.")]
+    OpenReferrals,
 }
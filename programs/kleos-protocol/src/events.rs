@@ -0,0 +1,302 @@
+use anchor_lang::prelude::*;
+
+use crate::{MultiplierSchedule, PendingProtocolUpdate, ProtocolUpdateGroup, Role, MAX_ITEMS};
+
+// Emitted through `emit_cpi!` so indexers can read them from inner instructions even when
+// program logs are truncated.
+
+#[event]
+pub struct ProtocolInitialized {
+    pub admin_authority: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub timelock_delay_secs: i64,
+    pub emergency_timeout_secs: i64,
+    pub claim_window_secs: i64,
+}
+
+#[event]
+pub struct ProtocolUpdateQueued {
+    pub queued_by: Pubkey,
+    pub update: PendingProtocolUpdate,
+}
+
+#[event]
+pub struct ProtocolUpdateCancelled {
+    pub cancelled_by: Pubkey,
+    pub group: ProtocolUpdateGroup,
+    /// What is still queued after the cancellation, if anything.
+    pub pending_update: Option<PendingProtocolUpdate>,
+}
+
+#[event]
+pub struct ProtocolUpdated {
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub max_creator_fee_bps: u16,
+    pub referral_fee_bps: u16,
+    pub scoring_authority: Pubkey,
+    pub timelock_delay_secs: i64,
+    pub emergency_timeout_secs: i64,
+    pub claim_window_secs: i64,
+}
+
+#[event]
+pub struct ProtocolPauseUpdated {
+    pub pauser: Pubkey,
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub creator: Pubkey,
+    pub creator_bond: u64,
+    pub token_mint: Pubkey,
+    pub is_native: bool,
+    pub items_hash: [u8; 32],
+    pub item_count: u8,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub multiplier_schedule: MultiplierSchedule,
+}
+
+#[event]
+pub struct MarketEdited {
+    pub market: Pubkey,
+    pub items_hash: [u8; 32],
+    pub item_count: u8,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub multiplier_schedule: MultiplierSchedule,
+}
+
+#[event]
+pub struct MarketOpened {
+    pub market: Pubkey,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct MarketClosed {
+    pub market: Pubkey,
+    pub total_raw_stake: u64,
+    pub total_effective_stake: u128,
+    pub effective_stake_per_item: [u128; MAX_ITEMS],
+}
+
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub winning_item_index: u8,
+    pub effective_stake_per_item: [u128; MAX_ITEMS],
+}
+
+#[event]
+pub struct MarketSettled {
+    pub market: Pubkey,
+    pub winning_item_index: u8,
+    pub total_raw_stake: u64,
+    pub protocol_fee_amount: u64,
+    pub creator_fee_amount: u64,
+    pub referral_reserve: u64,
+    pub distributable_pool: u64,
+    pub claim_deadline_ts: i64,
+    pub effective_stake_per_item: [u128; MAX_ITEMS],
+}
+
+#[event]
+pub struct PositionPlaced {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub selected_item_index: u8,
+    pub raw_stake: u64,
+    pub effective_stake: u128,
+    /// True if this deposit added to a position the user already held on the item.
+    pub top_up: bool,
+    pub position_raw_stake: u64,
+    pub referrer: Option<Pubkey>,
+    pub total_raw_stake: u64,
+    pub total_effective_stake: u128,
+    pub effective_stake_per_item: [u128; MAX_ITEMS],
+}
+
+#[event]
+pub struct PayoutClaimed {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub selected_item_index: u8,
+    pub payout: u64,
+    pub total_claimed: u64,
+    pub positions_claimed: u64,
+    pub effective_stake_per_item: [u128; MAX_ITEMS],
+}
+
+#[event]
+pub struct MarketCancelled {
+    pub market: Pubkey,
+    pub total_raw_stake: u64,
+    pub effective_stake_per_item: [u128; MAX_ITEMS],
+}
+
+#[event]
+pub struct PositionRefunded {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub selected_item_index: u8,
+    pub raw_stake: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct AttestationReceiptClosed {
+    pub receipt: Pubkey,
+    pub payer: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct SettledMarketClosed {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    /// Vault balance moved to the treasury before closing.
+    pub dust: u64,
+}
+
+#[event]
+pub struct EmptyMarketClosed {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    /// Vault balance moved to the treasury before closing.
+    pub dust: u64,
+}
+
+#[event]
+pub struct MarketSwept {
+    pub market: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MarketFeeLowered {
+    pub market: Pubkey,
+    pub previous_fee_bps: u16,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub min_stake: u64,
+    pub max_stake: u64,
+    pub creator_bond: u64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin_authority: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminProposalCancelled {
+    pub admin_authority: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub admin_authority: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub granted_by: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub authority: Pubkey,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct MarketFreezeUpdated {
+    pub market: Pubkey,
+    pub frozen: bool,
+    pub pauser: Pubkey,
+}
+
+#[event]
+pub struct EmergencyWithdrawal {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub selected_item_index: u8,
+    pub raw_stake: u64,
+    /// True when this withdrawal moved the market to `Cancelled`.
+    pub market_cancelled: bool,
+}
+
+#[event]
+pub struct CreatorBondReleased {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorBondSlashed {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub slashed_by: Pubkey,
+}
+
+#[event]
+pub struct ReferrerAccountCreated {
+    pub referrer_account: Pubkey,
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewardCollected {
+    pub market: Pubkey,
+    pub referrer_account: Pubkey,
+    pub referrer: Pubkey,
+    pub referred_raw_stake: u64,
+    /// Amount credited to the referrer account; zero once claims have closed.
+    pub reward: u64,
+    pub collected_by: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewardsWithdrawn {
+    pub referrer_account: Pubkey,
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::AdminTransferred, Protocol};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        constraint = protocol.pending_admin.is_some() @ ProtocolError::NoPendingAdmin,
+        constraint = protocol.pending_admin == Some(pending_admin.key()) @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<AdminTransferred> {
+        let previous_admin = self.protocol.admin_authority;
+
+        self.protocol.admin_authority = self.pending_admin.key();
+        self.protocol.pending_admin = None;
+
+        Ok(AdminTransferred {
+            previous_admin,
+            admin_authority: self.protocol.admin_authority,
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::AdminProposalCancelled, Protocol};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAdminProposal<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> CancelAdminProposal<'info> {
+    pub fn cancel_admin_proposal(&mut self) -> Result<AdminProposalCancelled> {
+        let pending_admin = self
+            .protocol
+            .pending_admin
+            .take()
+            .ok_or(ProtocolError::NoPendingAdmin)?;
+
+        Ok(AdminProposalCancelled {
+            admin_authority: self.protocol.admin_authority,
+            pending_admin,
+        })
+    }
+}
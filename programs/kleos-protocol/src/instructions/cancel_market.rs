@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::MarketCancelled, Market, MarketStatus, Protocol};
+
+/// Void a market before settlement. Stakers recover their raw stake through `refund_position`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(mut)]
+    pub market_operator: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = market_operator @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> CancelMarket<'info> {
+    pub fn cancel_market(&mut self) -> Result<MarketCancelled> {
+        // Funds have not moved until settlement, so any earlier state can be unwound
+        require!(
+            matches!(
+                self.market.status,
+                MarketStatus::Draft
+                    | MarketStatus::Open
+                    | MarketStatus::Closed
+                    | MarketStatus::Resolved
+            ),
+            ProtocolError::InvalidMarketState
+        );
+
+        // Transition state
+        self.market.status = MarketStatus::Cancelled;
+
+        Ok(MarketCancelled {
+            market: self.market.key(),
+            total_raw_stake: self.market.total_raw_stake,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::ProtocolUpdateCancelled, Protocol, ProtocolUpdateGroup};
+
+/// Drop one group of a queued protocol update, leaving the other group's timer running. Each
+/// group can only be cancelled by the role that queues it: the fee manager for the fees, the
+/// admin for everything else.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelProtocolUpdate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> CancelProtocolUpdate<'info> {
+    pub fn cancel_protocol_update(
+        &mut self,
+        group: ProtocolUpdateGroup,
+    ) -> Result<ProtocolUpdateCancelled> {
+        let authority = self.authority.key();
+
+        require_keys_eq!(
+            authority,
+            self.protocol.update_authority(group),
+            ProtocolError::Unauthorized
+        );
+
+        self.protocol.cancel_pending_update(group)?;
+
+        Ok(ProtocolUpdateCancelled {
+            cancelled_by: authority,
+            group,
+            pending_update: self.protocol.pending_update.clone(),
+        })
+    }
+}
@@ -1,22 +1,37 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::{errors::ProtocolError, Market, MarketStatus, Position};
+use crate::{
+    errors::ProtocolError, events::PayoutClaimed, Market, MarketStatus, Position, Protocol,
+    PAUSE_CLAIMS,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimPayout<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
     #[account(mut)]
     pub market: Account<'info, Market>,
 
     #[account(
         mut,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
         bump = position.bump,
         has_one = market,
-        has_one = user    
+        has_one = user
     )]
     pub position: Account<'info, Position>,
 
@@ -31,16 +46,29 @@ pub struct ClaimPayout<'info> {
       mut,
       constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> ClaimPayout<'info> {
-    pub fn claim_payout(&mut self, bumps: ClaimPayoutBumps) -> Result<()> {
+    pub fn claim_payout(
+        &mut self,
+        close_position: bool,
+        bumps: &ClaimPayoutBumps,
+    ) -> Result<PayoutClaimed> {
+        // Claims must not be paused
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
         // Market must be settled
         require!(
             self.market.status == MarketStatus::Settled,
@@ -53,28 +81,23 @@ impl<'info> ClaimPayout<'info> {
         // Prevent double claim
         require!(!self.position.claimed, ProtocolError::AlreadyClaimed);
 
-        // position.market == market.key() (has_one = market)
-        // vault.key() == market.vault (constraint below)
+        // Claims close at the deadline; leftovers are swept to treasury
+        let current_time = Clock::get()?.unix_timestamp;
+
         require!(
-            self.user_token_account.mint == self.market.token_mint,
-            ProtocolError::InvalidStakeAmount
+            current_time <= self.market.claim_deadline_ts,
+            ProtocolError::ClaimDeadlinePassed
         );
 
+        // position.market == market.key() (has_one = market)
+        // vault.key() == market.vault (constraint below)
         require!(
-            self.market.total_effective_stake > 0,
+            self.user_token_account.mint == self.market.token_mint,
             ProtocolError::InvalidStakeAmount
         );
 
-        // Payout: (position.effective_stake / market.total_effective_stake) × market.distributable_pool
-        let payout = self
-            .position
-            .effective_stake
-            .checked_mul(self.market.distributable_pool as u128)
-            .ok_or(ProtocolError::MathOverflow)?
-            .checked_div(self.market.total_effective_stake)
-            .ok_or(ProtocolError::MathOverflow)?;
-
-        let payout_u64: u64 = payout.try_into().map_err(|_| ProtocolError::MathOverflow)?;
+        // Only positions on the winning item are paid (or everyone is refunded if nobody backed it)
+        let payout_u64 = self.market.record_claim(&self.position)?;
 
         // Mark claimed BEFORE transfer
         self.position.claimed = true;
@@ -89,17 +112,37 @@ impl<'info> ClaimPayout<'info> {
 
             let cpi_ctx = CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
                     to: self.user_token_account.to_account_info(),
                     authority: self.vault_authority.to_account_info(),
                 },
                 signer,
             );
 
-            anchor_spl::token::transfer(cpi_ctx, payout_u64)?;
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                payout_u64,
+                self.token_mint.decimals,
+            )?;
+        }
+
+        // Optionally return the position's rent to the user
+        if close_position {
+            self.position.close(self.user.to_account_info())?;
+            self.market.record_position_closed()?;
         }
 
-        Ok(())
+        Ok(PayoutClaimed {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index: self.position.selected_item_index,
+            payout: payout_u64,
+            total_claimed: self.market.total_claimed,
+            positions_claimed: self.market.positions_claimed,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
     }
 }
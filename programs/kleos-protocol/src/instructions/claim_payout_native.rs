@@ -1,18 +1,33 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ProtocolError, Market, MarketStatus, Position};
+use crate::{
+    errors::ProtocolError, events::PayoutClaimed, Market, MarketStatus, Position, Protocol,
+    PAUSE_CLAIMS,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimPayoutNative<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
     #[account(mut)]
     pub market: Account<'info, Market>,
 
     #[account(
         mut,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
         bump = position.bump,
         has_one = market,
         has_one = user
@@ -31,27 +46,27 @@ pub struct ClaimPayoutNative<'info> {
 }
 
 impl<'info> ClaimPayoutNative<'info> {
-    pub fn claim_payout_native(&mut self, bumps: ClaimPayoutNativeBumps) -> Result<()> {
+    pub fn claim_payout_native(
+        &mut self,
+        close_position: bool,
+        bumps: &ClaimPayoutNativeBumps,
+    ) -> Result<PayoutClaimed> {
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
         require!(
             self.market.status == MarketStatus::Settled,
             ProtocolError::InvalidMarketState
         );
         require!(!self.position.claimed, ProtocolError::AlreadyClaimed);
+
+        let current_time = Clock::get()?.unix_timestamp;
         require!(
-            self.market.total_effective_stake > 0,
-            ProtocolError::InvalidStakeAmount
+            current_time <= self.market.claim_deadline_ts,
+            ProtocolError::ClaimDeadlinePassed
         );
         require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
 
-        let payout = self
-            .position
-            .effective_stake
-            .checked_mul(self.market.distributable_pool as u128)
-            .ok_or(ProtocolError::MathOverflow)?
-            .checked_div(self.market.total_effective_stake)
-            .ok_or(ProtocolError::MathOverflow)?;
-
-        let payout_u64: u64 = payout.try_into().map_err(|_| ProtocolError::MathOverflow)?;
+        let payout_u64 = self.market.record_claim(&self.position)?;
 
         self.position.claimed = true;
 
@@ -80,6 +95,20 @@ impl<'info> ClaimPayoutNative<'info> {
             )?;
         }
 
-        Ok(())
+        if close_position {
+            self.position.close(self.user.to_account_info())?;
+            self.market.record_position_closed()?;
+        }
+
+        Ok(PayoutClaimed {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index: self.position.selected_item_index,
+            payout: payout_u64,
+            total_claimed: self.market.total_claimed,
+            positions_claimed: self.market.positions_claimed,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
     }
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::AttestationReceiptClosed, AttestationReceipt};
+
+/// Close the receipt of an expired attestation and return its rent to whoever paid for it.
+/// Permissionless: an expired attestation is rejected anyway, so the receipt no longer guards
+/// anything.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseAttestationReceipt<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = payer,
+        close = payer
+    )]
+    pub attestation_receipt: Account<'info, AttestationReceipt>,
+
+    /// CHECK: Receives the receipt's rent; must match attestation_receipt.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseAttestationReceipt<'info> {
+    pub fn close_attestation_receipt(&mut self) -> Result<AttestationReceiptClosed> {
+        // Attestation must have expired so it cannot be replayed once the receipt is gone
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            current_time > self.attestation_receipt.expiry_ts,
+            ProtocolError::AttestationNotExpired
+        );
+
+        Ok(AttestationReceiptClosed {
+            receipt: self.attestation_receipt.key(),
+            payer: self.payer.key(),
+            closed_by: self.signer.key(),
+        })
+    }
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    errors::ProtocolError, events::EmptyMarketClosed, Market, MarketItems, MarketStatus, Protocol,
+    PAUSE_SETTLEMENT,
+};
+
+/// Terminal path for an SPL token market nobody joined, which can never be settled, or a cancelled
+/// market whose positions have all been refunded and closed and whose referral accounts have
+/// been collected. Closes the vault, the item list and the market, returning all rent to the
+/// market creator. Tokens left in the vault go to the treasury so the vault can be closed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseEmptyMarket<'info> {
+    #[account(mut)]
+    pub market_operator: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = market_operator @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_items", market.key().as_ref()],
+        bump = market_items.bump,
+        close = creator
+    )]
+    pub market_items: Account<'info, MarketItems>,
+
+    /// CHECK: Receives rent; must match market.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.token_mint,
+        associated_token::authority = protocol.treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseEmptyMarket<'info> {
+    pub fn close_empty_market(
+        &mut self,
+        bumps: &CloseEmptyMarketBumps,
+    ) -> Result<EmptyMarketClosed> {
+        // Closing moves what is left in the vault, so it honours the settlement pause
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        // Must no longer accept positions
+        require!(
+            matches!(
+                self.market.status,
+                MarketStatus::Draft
+                    | MarketStatus::Closed
+                    | MarketStatus::Resolved
+                    | MarketStatus::Cancelled
+            ),
+            ProtocolError::InvalidMarketState
+        );
+
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        // Creator bond must be released or slashed before the vault is emptied
+        require!(
+            self.market.creator_bond == 0,
+            ProtocolError::CreatorBondOutstanding
+        );
+
+        // Nobody may have joined, or every position of a cancelled market has been refunded
+        require!(self.market.is_drained(), ProtocolError::MarketNotEmpty);
+
+        // Refunded positions must be closed first so their rent goes back to their owners
+        require!(
+            self.market.positions_closed == self.market.position_count,
+            ProtocolError::OpenPositions
+        );
+
+        // Referral accounts too, so their rent and any reward reach the referrers
+        require!(
+            self.market.referrals_collected == self.market.referral_count,
+            ProtocolError::OpenReferrals
+        );
+
+        let market_key = self.market.key();
+
+        let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+        let signer = &[seeds];
+
+        // Move stray tokens to treasury so the vault can be closed
+        let dust = self.vault.amount;
+
+        if dust > 0 {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.treasury_token_account.to_account_info(),
+                    authority: self.vault_authority.to_account_info(),
+                },
+                signer,
+            );
+
+            anchor_spl::token_interface::transfer_checked(cpi_ctx, dust, self.token_mint.decimals)?;
+        }
+
+        // Close vault, returning its rent to the creator
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.creator.to_account_info(),
+                authority: self.vault_authority.to_account_info(),
+            },
+            signer,
+        );
+
+        anchor_spl::token_interface::close_account(cpi_ctx)?;
+
+        Ok(EmptyMarketClosed {
+            market: self.market.key(),
+            creator: self.market.creator,
+            dust,
+        })
+    }
+}
@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ProtocolError, Market, MarketStatus};
+use crate::{
+    errors::ProtocolError, events::MarketClosed, Market, MarketStatus, Protocol, PAUSE_SETTLEMENT,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CloseMarket<'info> {
     pub signer: Signer<'info>,
 
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
     #[account(mut)]
     pub market: Account<'info, Market>,
 
@@ -13,7 +22,13 @@ pub struct CloseMarket<'info> {
 }
 
 impl<'info> CloseMarket<'info> {
-    pub fn close_market(&mut self) -> Result<()> {
+    pub fn close_market(&mut self) -> Result<MarketClosed> {
+        // Settlement must not be paused
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
         // Market must be Open
         require!(
             self.market.status == MarketStatus::Open,
@@ -31,6 +46,11 @@ impl<'info> CloseMarket<'info> {
         // Transition state
         self.market.status = MarketStatus::Closed;
 
-        Ok(())
+        Ok(MarketClosed {
+            market: self.market.key(),
+            total_raw_stake: self.market.total_raw_stake,
+            total_effective_stake: self.market.total_effective_stake,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
     }
 }
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::PositionClosed, Market, Position};
+
+/// Close a position that can no longer pay out and return its rent to the owner: one that was
+/// refunded, or one in a settled market that has claimed or missed the claim deadline.
+/// Permissionless, so the creator can clear positions before closing the market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
+        bump = position.bump,
+        has_one = market,
+        has_one = user,
+        close = user
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Receives the position's rent; must match position.user
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+}
+
+impl<'info> ClosePosition<'info> {
+    pub fn close_position(&mut self) -> Result<PositionClosed> {
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        // Position must have been paid, refunded, or left behind by the claim deadline
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.market.position_closable(&self.position, current_time),
+            ProtocolError::PositionNotClosable
+        );
+
+        self.market.record_position_closed()?;
+
+        Ok(PositionClosed {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            closed_by: self.signer.key(),
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::SettledMarketClosed, Market, MarketItems, MarketStatus, Protocol,
+    PAUSE_CLAIMS, PAUSE_SETTLEMENT,
+};
+
+/// Close a native-SOL market once every position has claimed or the claim deadline has passed.
+/// Lamports left in the vault PDA go to the treasury; rent for the market and its items is
+/// returned to the market creator.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseSettledMarketNative<'info> {
+    #[account(mut)]
+    pub market_operator: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = market_operator @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        close = creator
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_items", market.key().as_ref()],
+        bump = market_items.bump,
+        close = creator
+    )]
+    pub market_items: Account<'info, MarketItems>,
+
+    /// CHECK: Receives rent; must match market.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the configured protocol treasury
+    #[account(
+        mut,
+        address = protocol.treasury @ ProtocolError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseSettledMarketNative<'info> {
+    pub fn close_settled_market_native(
+        &mut self,
+        bumps: &CloseSettledMarketNativeBumps,
+    ) -> Result<SettledMarketClosed> {
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT | PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+        require!(
+            self.market.status == MarketStatus::Settled,
+            ProtocolError::InvalidMarketState
+        );
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+        require!(
+            self.market.creator_bond == 0,
+            ProtocolError::CreatorBondOutstanding
+        );
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            self.market.positions_claimed == self.market.position_count
+                || current_time > self.market.claim_deadline_ts,
+            ProtocolError::UnclaimedPositions
+        );
+        require!(
+            self.market.positions_closed == self.market.position_count,
+            ProtocolError::OpenPositions
+        );
+        require!(
+            self.market.referrals_collected == self.market.referral_count,
+            ProtocolError::OpenReferrals
+        );
+
+        let dust = self.vault.lamports();
+
+        if dust > 0 {
+            let market_key = self.market.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                market_key.as_ref(),
+                &[bumps.vault],
+            ]];
+
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                &self.vault.key(),
+                &self.treasury.key(),
+                dust,
+            );
+
+            anchor_lang::solana_program::program::invoke_signed(
+                &transfer_ix,
+                &[
+                    self.vault.to_account_info(),
+                    self.treasury.to_account_info(),
+                    self.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
+        Ok(SettledMarketClosed {
+            market: self.market.key(),
+            creator: self.market.creator,
+            dust,
+        })
+    }
+}
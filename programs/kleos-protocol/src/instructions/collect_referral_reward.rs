@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    errors::ProtocolError, events::ReferralRewardCollected, Market, MarketReferral, Protocol,
+    ReferrerAccount, PAUSE_CLAIMS,
+};
+
+/// Credit a referrer with its share of an SPL market's referral reserve and close its
+/// `MarketReferral`, returning the rent to whoever opened it. Permissionless, so the reward
+/// never waits on the referred positions claiming. After the claim deadline, or on a cancelled
+/// market, it only closes the account.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectReferralReward<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", market.key().as_ref(), referrer_account.key().as_ref()],
+        bump = market_referral.bump,
+        has_one = market,
+        has_one = referrer_account,
+        has_one = payer,
+        close = payer
+    )]
+    pub market_referral: Account<'info, MarketReferral>,
+
+    #[account(mut)]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    #[account(
+      mut,
+      constraint = referrer_vault.key() == referrer_account.vault @ ProtocolError::InvalidReferrer
+    )]
+    pub referrer_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Receives the referral account's rent; must match market_referral.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CollectReferralReward<'info> {
+    pub fn collect_referral_reward(
+        &mut self,
+        bumps: &CollectReferralRewardBumps,
+    ) -> Result<ReferralRewardCollected> {
+        // Rewards are payouts, so they honour the claims pause
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        // Reward is this referrer's share of the reserve, or nothing once claims have closed
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let reward = self
+            .market
+            .record_referral_collected(self.market_referral.referred_raw_stake, current_time)?;
+
+        let mut credited_reward = 0;
+
+        if reward > 0 {
+            let referrer_vault_before = self.referrer_vault.amount;
+
+            let market_key = self.market.key();
+
+            let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+            let signer = &[seeds];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.referrer_vault.to_account_info(),
+                    authority: self.vault_authority.to_account_info(),
+                },
+                signer,
+            );
+
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                reward,
+                self.token_mint.decimals,
+            )?;
+
+            // Credit what actually arrived; transfer-fee mints withhold part of the amount
+            self.referrer_vault.reload()?;
+
+            credited_reward = self
+                .referrer_vault
+                .amount
+                .checked_sub(referrer_vault_before)
+                .ok_or(ProtocolError::MathOverflow)?;
+
+            self.referrer_account.credit(credited_reward)?;
+        }
+
+        Ok(ReferralRewardCollected {
+            market: self.market.key(),
+            referrer_account: self.referrer_account.key(),
+            referrer: self.referrer_account.referrer,
+            referred_raw_stake: self.market_referral.referred_raw_stake,
+            reward: credited_reward,
+            collected_by: self.signer.key(),
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::ReferralRewardCollected, Market, MarketReferral, Protocol,
+    ReferrerAccount, PAUSE_CLAIMS,
+};
+
+/// Native-SOL counterpart of `collect_referral_reward`. Moves the reward from the vault PDA
+/// onto the referrer account.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectReferralRewardNative<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", market.key().as_ref(), referrer_account.key().as_ref()],
+        bump = market_referral.bump,
+        has_one = market,
+        has_one = referrer_account,
+        has_one = payer,
+        close = payer
+    )]
+    pub market_referral: Account<'info, MarketReferral>,
+
+    #[account(mut)]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the referral account's rent; must match market_referral.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CollectReferralRewardNative<'info> {
+    pub fn collect_referral_reward_native(
+        &mut self,
+        bumps: &CollectReferralRewardNativeBumps,
+    ) -> Result<ReferralRewardCollected> {
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let reward = self
+            .market
+            .record_referral_collected(self.market_referral.referred_raw_stake, current_time)?;
+
+        if reward > 0 {
+            self.referrer_account.credit(reward)?;
+
+            let market_key = self.market.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                market_key.as_ref(),
+                &[bumps.vault],
+            ]];
+
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                &self.vault.key(),
+                &self.referrer_account.key(),
+                reward,
+            );
+
+            anchor_lang::solana_program::program::invoke_signed(
+                &transfer_ix,
+                &[
+                    self.vault.to_account_info(),
+                    self.referrer_account.to_account_info(),
+                    self.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
+        Ok(ReferralRewardCollected {
+            market: self.market.key(),
+            referrer_account: self.referrer_account.key(),
+            referrer: self.referrer_account.referrer,
+            referred_raw_stake: self.market_referral.referred_raw_stake,
+            reward,
+            collected_by: self.signer.key(),
+        })
+    }
+}
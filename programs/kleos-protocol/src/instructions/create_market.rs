@@ -1,37 +1,54 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
 
 use crate::{
     Protocol,
     Market,
+    MarketItems,
     MarketStatus,
+    MintConfig,
+    MultiplierSchedule,
     errors::ProtocolError,
+    events::MarketCreated,
     constants::MAX_ITEMS,
+    token_extensions::ensure_supported_mint,
+    PAUSE_MARKET_CREATION,
 };
 
+/// Create an SPL token market. Anyone may create one; wallets other than the market operator
+/// post the mint's creator bond into the vault.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateMarket<'info> {
     #[account(mut)]
-    pub admin_authority: Signer<'info>,
+    pub creator: Signer<'info>,
 
     #[account(
         mut,
         seeds = [b"protocol"],
         bump = protocol.bump,
-        has_one = admin_authority @ ProtocolError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
     #[account(
         init,
-        payer = admin_authority,
+        payer = creator,
         space = 8 + Market::INIT_SPACE,
         seeds = [b"market", protocol.market_count.to_le_bytes().as_ref()],
         bump
     )]
     pub market: Account<'info, Market>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketItems::INIT_SPACE,
+        seeds = [b"market_items", market.key().as_ref()],
+        bump
+    )]
+    pub market_items: Account<'info, MarketItems>,
+
     /// CHECK: PDA authority for vault
     #[account(
         seeds = [b"vault", market.key().as_ref()],
@@ -41,31 +58,52 @@ pub struct CreateMarket<'info> {
 
     #[account(
         init,
-        payer = admin_authority,
+        payer = creator,
         associated_token::mint = token_mint,
-        associated_token::authority = vault_authority
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"mint_config", token_mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.enabled @ ProtocolError::MintNotAllowed
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Source of the creator bond; required unless the creator is the market operator
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = creator,
+        token::token_program = token_program
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateMarket<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         &mut self,
         start_ts: i64,
         end_ts: i64,
         items_hash: [u8; 32],
         item_count: u8,
-        bumps: CreateMarketBumps,
-    ) -> Result<()> {
+        item_labels: Vec<String>,
+        multiplier_schedule: MultiplierSchedule,
+        creator_fee_bps: u16,
+        bumps: &CreateMarketBumps,
+    ) -> Result<MarketCreated> {
 
-        // Protocol must not be paused
-        require!(!self.protocol.paused, ProtocolError::ProtocolPaused);
+        // Market creation must not be paused
+        self.protocol.require_unpaused(PAUSE_MARKET_CREATION)?;
 
         // Validate timestamps
         require!(end_ts > start_ts, ProtocolError::InvalidTimestamp);
@@ -77,6 +115,58 @@ impl<'info> CreateMarket<'info> {
             ProtocolError::InvalidItemIndex
         );
 
+        // Labels must match the declared count and hash
+        MarketItems::verify_labels(&item_labels, item_count, &items_hash)?;
+
+        // Validate multiplier schedule
+        multiplier_schedule.validate()?;
+
+        // Creator fee share is capped by the protocol
+        require!(
+            creator_fee_bps <= self.protocol.max_creator_fee_bps,
+            ProtocolError::InvalidCreatorFeeBps
+        );
+
+        // Reject Token-2022 mints whose extensions could move or lock vault funds
+        ensure_supported_mint(&self.token_mint.to_account_info())?;
+
+        // Creators other than the market operator post a bond
+        let required_bond = self
+            .mint_config
+            .creator_bond_for(self.creator.key() == self.protocol.market_operator)?;
+
+        let mut creator_bond = 0;
+
+        if required_bond > 0 {
+            let creator_token_account = self
+                .creator_token_account
+                .as_ref()
+                .ok_or(ProtocolError::MissingCreatorTokenAccount)?;
+
+            let cpi_ctx = CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: creator_token_account.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.vault.to_account_info(),
+                    authority: self.creator.to_account_info(),
+                },
+            );
+
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                required_bond,
+                self.token_mint.decimals,
+            )?;
+
+            // The vault was created empty above, so its balance is what actually arrived;
+            // transfer-fee mints withhold part of the amount
+            self.vault.reload()?;
+            creator_bond = self.vault.amount;
+
+            require!(creator_bond > 0, ProtocolError::InvalidStakeAmount);
+        }
+
         let market_id = self.protocol.market_count;
 
         self.market.set_inner(Market {
@@ -86,19 +176,46 @@ impl<'info> CreateMarket<'info> {
             start_ts,
             end_ts,
             status: MarketStatus::Draft,
+            frozen: false,
 
             total_raw_stake: 0,
             total_effective_stake: 0,
             effective_stake_per_item: [0u128; MAX_ITEMS],
+            multiplier_schedule,
+            fee_bps: self.protocol.protocol_fee_bps,
+            creator_fee_bps,
             protocol_fee_amount: 0,
+            creator_fee_amount: 0,
+            referral_fee_bps: self.protocol.referral_fee_bps,
+            referred_raw_stake: 0,
+            referral_reserve: 0,
+            referral_rewards_credited: 0,
+            referral_count: 0,
+            referrals_collected: 0,
             distributable_pool: 0,
+            winning_item_index: None,
+            position_count: 0,
+            positions_claimed: 0,
+            positions_refunded: 0,
+            positions_closed: 0,
+            total_claimed: 0,
+            claimed_winning_stake: 0,
+            claim_deadline_ts: 0,
 
             token_mint: self.token_mint.key(),
             vault: self.vault.key(),
+            creator: self.creator.key(),
+            creator_bond,
             bump: bumps.market,
             is_native: false,
         });
 
+        self.market_items.set_inner(MarketItems {
+            market: self.market.key(),
+            labels: item_labels,
+            bump: bumps.market_items,
+        });
+
         // Increment market counter
         self.protocol.market_count = self
             .protocol
@@ -106,6 +223,20 @@ impl<'info> CreateMarket<'info> {
             .checked_add(1)
             .ok_or(ProtocolError::MathOverflow)?;
 
-        Ok(())
+        Ok(MarketCreated {
+            market: self.market.key(),
+            market_id: self.market.market_id,
+            creator: self.market.creator,
+            creator_bond: self.market.creator_bond,
+            token_mint: self.market.token_mint,
+            is_native: self.market.is_native,
+            items_hash: self.market.items_hash,
+            item_count: self.market.item_count,
+            start_ts: self.market.start_ts,
+            end_ts: self.market.end_ts,
+            fee_bps: self.market.fee_bps,
+            creator_fee_bps: self.market.creator_fee_bps,
+            multiplier_schedule: self.market.multiplier_schedule.clone(),
+        })
     }
 }
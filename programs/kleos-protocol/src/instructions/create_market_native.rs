@@ -2,56 +2,84 @@ use anchor_lang::prelude::*;
 
 use crate::{
     errors::ProtocolError,
+    events::MarketCreated,
     constants::MAX_ITEMS,
     Market,
+    MarketItems,
     MarketStatus,
+    MintConfig,
+    MultiplierSchedule,
     Protocol,
+    PAUSE_MARKET_CREATION,
 };
 
 /// Create a market that accepts native SOL (lamports) instead of SPL tokens.
 /// The vault is the vault_authority PDA itself, which holds lamports directly.
+/// Wallets other than the market operator post the creator bond into the vault.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateMarketNative<'info> {
     #[account(mut)]
-    pub admin_authority: Signer<'info>,
+    pub creator: Signer<'info>,
 
     #[account(
         mut,
         seeds = [b"protocol"],
         bump = protocol.bump,
-        has_one = admin_authority @ ProtocolError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
     #[account(
         init,
-        payer = admin_authority,
+        payer = creator,
         space = 8 + Market::INIT_SPACE,
         seeds = [b"market", protocol.market_count.to_le_bytes().as_ref()],
         bump
     )]
     pub market: Account<'info, Market>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketItems::INIT_SPACE,
+        seeds = [b"market_items", market.key().as_ref()],
+        bump
+    )]
+    pub market_items: Account<'info, MarketItems>,
+
     /// CHECK: PDA for vault; when is_native, this PDA holds lamports directly; validated by seeds
     #[account(
+        mut,
         seeds = [b"vault", market.key().as_ref()],
         bump
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
+    /// Native SOL markets are keyed by the system program id
+    #[account(
+        seeds = [b"mint_config", system_program.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.enabled @ ProtocolError::MintNotAllowed
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateMarketNative<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market_native(
         &mut self,
         start_ts: i64,
         end_ts: i64,
         items_hash: [u8; 32],
         item_count: u8,
-        bumps: CreateMarketNativeBumps,
-    ) -> Result<()> {
-        require!(!self.protocol.paused, ProtocolError::ProtocolPaused);
+        item_labels: Vec<String>,
+        multiplier_schedule: MultiplierSchedule,
+        creator_fee_bps: u16,
+        bumps: &CreateMarketNativeBumps,
+    ) -> Result<MarketCreated> {
+        self.protocol.require_unpaused(PAUSE_MARKET_CREATION)?;
         require!(end_ts > start_ts, ProtocolError::InvalidTimestamp);
         require!(item_count > 1, ProtocolError::InvalidItemIndex);
         require!(
@@ -59,6 +87,28 @@ impl<'info> CreateMarketNative<'info> {
             ProtocolError::InvalidItemIndex
         );
 
+        MarketItems::verify_labels(&item_labels, item_count, &items_hash)?;
+        multiplier_schedule.validate()?;
+        require!(
+            creator_fee_bps <= self.protocol.max_creator_fee_bps,
+            ProtocolError::InvalidCreatorFeeBps
+        );
+
+        let creator_bond = self
+            .mint_config
+            .creator_bond_for(self.creator.key() == self.protocol.market_operator)?;
+
+        if creator_bond > 0 {
+            let cpi_ctx = CpiContext::new(
+                self.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: self.creator.to_account_info(),
+                    to: self.vault_authority.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, creator_bond)?;
+        }
+
         let market_id = self.protocol.market_count;
 
         self.market.set_inner(Market {
@@ -68,25 +118,66 @@ impl<'info> CreateMarketNative<'info> {
             start_ts,
             end_ts,
             status: MarketStatus::Draft,
+            frozen: false,
 
             total_raw_stake: 0,
             total_effective_stake: 0,
             effective_stake_per_item: [0u128; MAX_ITEMS],
+            multiplier_schedule,
+            fee_bps: self.protocol.protocol_fee_bps,
+            creator_fee_bps,
             protocol_fee_amount: 0,
+            creator_fee_amount: 0,
+            referral_fee_bps: self.protocol.referral_fee_bps,
+            referred_raw_stake: 0,
+            referral_reserve: 0,
+            referral_rewards_credited: 0,
+            referral_count: 0,
+            referrals_collected: 0,
             distributable_pool: 0,
+            winning_item_index: None,
+            position_count: 0,
+            positions_claimed: 0,
+            positions_refunded: 0,
+            positions_closed: 0,
+            total_claimed: 0,
+            claimed_winning_stake: 0,
+            claim_deadline_ts: 0,
 
             token_mint: anchor_lang::system_program::ID,
             vault: self.vault_authority.key(),
+            creator: self.creator.key(),
+            creator_bond,
             bump: bumps.market,
             is_native: true,
         });
 
+        self.market_items.set_inner(MarketItems {
+            market: self.market.key(),
+            labels: item_labels,
+            bump: bumps.market_items,
+        });
+
         self.protocol.market_count = self
             .protocol
             .market_count
             .checked_add(1)
             .ok_or(ProtocolError::MathOverflow)?;
 
-        Ok(())
+        Ok(MarketCreated {
+            market: self.market.key(),
+            market_id: self.market.market_id,
+            creator: self.market.creator,
+            creator_bond: self.market.creator_bond,
+            token_mint: self.market.token_mint,
+            is_native: self.market.is_native,
+            items_hash: self.market.items_hash,
+            item_count: self.market.item_count,
+            start_ts: self.market.start_ts,
+            end_ts: self.market.end_ts,
+            fee_bps: self.market.fee_bps,
+            creator_fee_bps: self.market.creator_fee_bps,
+            multiplier_schedule: self.market.multiplier_schedule.clone(),
+        })
     }
 }
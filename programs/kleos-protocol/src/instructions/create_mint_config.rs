@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::MintConfigUpdated, MintConfig, Protocol};
+
+/// Approve a stake mint for new markets. Use the system program id for native SOL.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct CreateMintConfig<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init,
+        payer = admin_authority,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [b"mint_config", mint.as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMintConfig<'info> {
+    pub fn create_mint_config(
+        &mut self,
+        mint: Pubkey,
+        min_stake: u64,
+        max_stake: u64,
+        creator_bond: u64,
+        bumps: &CreateMintConfigBumps,
+    ) -> Result<MintConfigUpdated> {
+        MintConfig::validate_bounds(min_stake, max_stake)?;
+
+        self.mint_config.set_inner(MintConfig {
+            mint,
+            enabled: true,
+            min_stake,
+            max_stake,
+            creator_bond,
+            bump: bumps.mint_config,
+        });
+
+        Ok(MintConfigUpdated {
+            mint,
+            enabled: true,
+            min_stake,
+            max_stake,
+            creator_bond,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{events::ReferrerAccountCreated, ReferrerAccount};
+
+/// Register as a referrer for an SPL stake mint. Positions can only name registered referrers,
+/// so claims always have somewhere to credit the reward.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferrerAccount<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerAccount::INIT_SPACE,
+        seeds = [b"referrer", referrer.key().as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    /// Holds credited rewards until the referrer withdraws them
+    #[account(
+        init,
+        payer = referrer,
+        associated_token::mint = token_mint,
+        associated_token::authority = referrer_account,
+        associated_token::token_program = token_program
+    )]
+    pub referrer_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateReferrerAccount<'info> {
+    pub fn create_referrer_account(
+        &mut self,
+        bumps: &CreateReferrerAccountBumps,
+    ) -> Result<ReferrerAccountCreated> {
+        self.referrer_account.set_inner(ReferrerAccount {
+            referrer: self.referrer.key(),
+            mint: self.token_mint.key(),
+            vault: self.referrer_vault.key(),
+            accrued: 0,
+            total_earned: 0,
+            bump: bumps.referrer_account,
+        });
+
+        Ok(ReferrerAccountCreated {
+            referrer_account: self.referrer_account.key(),
+            referrer: self.referrer.key(),
+            mint: self.token_mint.key(),
+        })
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ReferrerAccountCreated, ReferrerAccount};
+
+/// Register as a referrer for native SOL markets. Rewards are held as lamports on the
+/// referrer account itself.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferrerAccountNative<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// Native SOL is keyed by the system program id
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerAccount::INIT_SPACE,
+        seeds = [b"referrer", referrer.key().as_ref(), system_program.key().as_ref()],
+        bump
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateReferrerAccountNative<'info> {
+    pub fn create_referrer_account_native(
+        &mut self,
+        bumps: &CreateReferrerAccountNativeBumps,
+    ) -> Result<ReferrerAccountCreated> {
+        self.referrer_account.set_inner(ReferrerAccount {
+            referrer: self.referrer.key(),
+            mint: anchor_lang::system_program::ID,
+            vault: self.referrer_account.key(),
+            accrued: 0,
+            total_earned: 0,
+            bump: bumps.referrer_account,
+        });
+
+        Ok(ReferrerAccountCreated {
+            referrer_account: self.referrer_account.key(),
+            referrer: self.referrer.key(),
+            mint: anchor_lang::system_program::ID,
+        })
+    }
+}
@@ -1,22 +1,31 @@
 use anchor_lang::prelude::*;
 
-use crate::{Market, MarketStatus, Protocol, errors::ProtocolError};
+use crate::{Market, MarketItems, MarketStatus, MultiplierSchedule, Protocol, errors::ProtocolError, events::MarketEdited, constants::{MAX_ITEMS, PAUSE_MARKET_CREATION}};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct EditMarket<'info> {
     #[account(mut)]
-    pub admin_authority: Signer<'info>,
+    pub market_operator: Signer<'info>,
 
     #[account(
         seeds = [b"protocol"],
         bump = protocol.bump,
-        has_one = admin_authority @ ProtocolError::Unauthorized
+        has_one = market_operator @ ProtocolError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
     #[account(mut)]
     pub market: Account<'info, Market>,
 
+    #[account(
+        mut,
+        seeds = [b"market_items", market.key().as_ref()],
+        bump = market_items.bump,
+        has_one = market
+    )]
+    pub market_items: Account<'info, MarketItems>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -27,10 +36,12 @@ impl<'info> EditMarket<'info> {
         end_ts: i64,
         items_hash: [u8; 32],
         item_count: u8,
-    ) -> Result<()> {
+        item_labels: Vec<String>,
+        multiplier_schedule: MultiplierSchedule,
+    ) -> Result<MarketEdited> {
 
-        // Protocol must not be paused
-        require!(!self.protocol.paused, ProtocolError::ProtocolPaused);
+        // Market creation must not be paused
+        self.protocol.require_unpaused(PAUSE_MARKET_CREATION)?;
 
         // Market must be in Draft state
         require!(
@@ -49,13 +60,32 @@ impl<'info> EditMarket<'info> {
 
         // Validate item count
         require!(item_count > 1, ProtocolError::InvalidItemIndex);
+        require!(
+            item_count as usize <= MAX_ITEMS,
+            ProtocolError::InvalidItemIndex
+        );
+
+        // Labels must match the declared count and hash
+        MarketItems::verify_labels(&item_labels, item_count, &items_hash)?;
+
+        // Validate multiplier schedule
+        multiplier_schedule.validate()?;
 
         // Apply updates
         self.market.start_ts = start_ts;
         self.market.end_ts = end_ts;
         self.market.items_hash = items_hash;
         self.market.item_count = item_count;
+        self.market_items.labels = item_labels;
+        self.market.multiplier_schedule = multiplier_schedule;
 
-        Ok(())
+        Ok(MarketEdited {
+            market: self.market.key(),
+            items_hash: self.market.items_hash,
+            item_count: self.market.item_count,
+            start_ts: self.market.start_ts,
+            end_ts: self.market.end_ts,
+            multiplier_schedule: self.market.multiplier_schedule.clone(),
+        })
     }
 }
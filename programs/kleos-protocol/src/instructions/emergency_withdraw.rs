@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{errors::ProtocolError, events::EmergencyWithdrawal, Market, Position, Protocol};
+
+/// Return a position's raw stake from an SPL token market that was never settled, once the
+/// protocol's emergency timeout has passed since `end_ts`. Ignores pause flags and market
+/// freezes: this is the path out when the protocol is stuck.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
+        bump = position.bump,
+        has_one = market,
+        has_one = user
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> EmergencyWithdraw<'info> {
+    pub fn emergency_withdraw(
+        &mut self,
+        bumps: &EmergencyWithdrawBumps,
+    ) -> Result<EmergencyWithdrawal> {
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        // Prevent double withdrawal
+        require!(!self.position.refunded, ProtocolError::AlreadyRefunded);
+
+        require!(
+            self.user_token_account.mint == self.market.token_mint,
+            ProtocolError::InvalidStakeAmount
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let market_cancelled = self
+            .market
+            .begin_emergency_withdrawal(self.protocol.emergency_timeout_secs, now)?;
+
+        let refund = self.position.raw_stake;
+
+        // Mark refunded BEFORE transfer
+        self.position.refunded = true;
+        self.market.record_refund()?;
+
+        if refund > 0 {
+            let market_key = self.market.key();
+
+            let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+            let signer = &[seeds];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.user_token_account.to_account_info(),
+                    authority: self.vault_authority.to_account_info(),
+                },
+                signer,
+            );
+
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                refund,
+                self.token_mint.decimals,
+            )?;
+        }
+
+        Ok(EmergencyWithdrawal {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index: self.position.selected_item_index,
+            raw_stake: refund,
+            market_cancelled,
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::EmergencyWithdrawal, Market, Position, Protocol};
+
+/// Native-SOL counterpart of `emergency_withdraw`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyWithdrawNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
+        bump = position.bump,
+        has_one = market,
+        has_one = user
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EmergencyWithdrawNative<'info> {
+    pub fn emergency_withdraw_native(
+        &mut self,
+        bumps: &EmergencyWithdrawNativeBumps,
+    ) -> Result<EmergencyWithdrawal> {
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+        require!(!self.position.refunded, ProtocolError::AlreadyRefunded);
+
+        let now = Clock::get()?.unix_timestamp;
+        let market_cancelled = self
+            .market
+            .begin_emergency_withdrawal(self.protocol.emergency_timeout_secs, now)?;
+
+        let refund = self.position.raw_stake;
+
+        self.position.refunded = true;
+        self.market.record_refund()?;
+
+        if refund > 0 {
+            let market_key = self.market.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                market_key.as_ref(),
+                &[bumps.vault],
+            ]];
+
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                &self.vault.key(),
+                &self.user.key(),
+                refund,
+            );
+
+            anchor_lang::solana_program::program::invoke_signed(
+                &transfer_ix,
+                &[
+                    self.vault.to_account_info(),
+                    self.user.to_account_info(),
+                    self.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
+        Ok(EmergencyWithdrawal {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index: self.position.selected_item_index,
+            raw_stake: refund,
+            market_cancelled,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::ProtocolUpdated, Protocol};
+
+/// Apply each group of queued fields whose timelock has elapsed; a group still waiting stays
+/// queued. Permissionless: the parameters were fixed when the update was queued.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProtocolUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> ExecuteProtocolUpdate<'info> {
+    pub fn execute_protocol_update(&mut self) -> Result<ProtocolUpdated> {
+        let mut update = self
+            .protocol
+            .pending_update
+            .clone()
+            .ok_or(ProtocolError::NoPendingUpdate)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let fee_due = update.fee_eta.is_some_and(|eta| now >= eta);
+        let admin_due = update.admin_eta.is_some_and(|eta| now >= eta);
+        require!(fee_due || admin_due, ProtocolError::TimelockNotElapsed);
+
+        let protocol = &mut self.protocol;
+
+        if fee_due {
+            protocol.protocol_fee_bps = update.protocol_fee_bps;
+            protocol.max_creator_fee_bps = update.max_creator_fee_bps;
+            protocol.referral_fee_bps = update.referral_fee_bps;
+            update.fee_eta = None;
+        }
+        if admin_due {
+            protocol.treasury = update.treasury;
+            protocol.scoring_authority = update.scoring_authority;
+            protocol.timelock_delay_secs = update.timelock_delay_secs;
+            protocol.emergency_timeout_secs = update.emergency_timeout_secs;
+            protocol.claim_window_secs = update.claim_window_secs;
+            update.admin_eta = None;
+        }
+
+        protocol.pending_update =
+            (update.fee_eta.is_some() || update.admin_eta.is_some()).then_some(update);
+
+        Ok(ProtocolUpdated {
+            treasury: protocol.treasury,
+            protocol_fee_bps: protocol.protocol_fee_bps,
+            max_creator_fee_bps: protocol.max_creator_fee_bps,
+            referral_fee_bps: protocol.referral_fee_bps,
+            scoring_authority: protocol.scoring_authority,
+            timelock_delay_secs: protocol.timelock_delay_secs,
+            emergency_timeout_secs: protocol.emergency_timeout_secs,
+            claim_window_secs: protocol.claim_window_secs,
+        })
+    }
+}
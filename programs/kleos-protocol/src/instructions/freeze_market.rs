@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::MarketFreezeUpdated, Market, Protocol};
+
+/// Halt a single market during an incident without pausing the rest of the protocol.
+/// Deposits, closing, settlement, claims and refunds on it fail until `unfreeze_market`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FreezeMarket<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = pauser @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> FreezeMarket<'info> {
+    pub fn freeze_market(&mut self) -> Result<MarketFreezeUpdated> {
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        self.market.frozen = true;
+
+        Ok(MarketFreezeUpdated {
+            market: self.market.key(),
+            frozen: true,
+            pauser: self.pauser.key(),
+        })
+    }
+}
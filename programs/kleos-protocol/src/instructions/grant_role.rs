@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::RoleGranted, Protocol, Role};
+
+/// Assign a role, replacing its current holder. Every change is emitted as an event so the
+/// full history of role holders can be rebuilt from transaction logs.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> GrantRole<'info> {
+    pub fn grant_role(&mut self, role: Role, authority: Pubkey) -> Result<RoleGranted> {
+        let previous_authority = self.protocol.role(role);
+        self.protocol.set_role(role, authority);
+
+        Ok(RoleGranted {
+            role,
+            previous_authority,
+            authority,
+            granted_by: self.admin_authority.key(),
+        })
+    }
+}
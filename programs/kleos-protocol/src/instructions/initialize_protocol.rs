@@ -1,6 +1,10 @@
-use crate::{errors::ProtocolError, Protocol};
+use crate::{
+    errors::ProtocolError, events::ProtocolInitialized, Protocol, DEFAULT_CLAIM_WINDOW_SECS,
+    DEFAULT_EMERGENCY_TIMEOUT_SECS,
+};
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeProtocol<'info> {
     #[account(mut)]
@@ -23,22 +27,42 @@ impl<'info> InitializeProtocol<'info> {
         &mut self,
         protocol_fee_bps: u16,
         treasury: Pubkey,
-        bumps: InitializeProtocolBumps,
-    ) -> Result<()> {
+        timelock_delay_secs: i64,
+        bumps: &InitializeProtocolBumps,
+    ) -> Result<ProtocolInitialized> {
         require!(
             protocol_fee_bps <= 10_000,
             ProtocolError::InvalidProtocolFeeBps
         );
+        Protocol::validate_timelock_delay(timelock_delay_secs)?;
 
         self.protocol.set_inner(Protocol {
             admin_authority: self.admin.key(),
+            pending_admin: None,
+            pauser: self.admin.key(),
+            market_operator: self.admin.key(),
+            fee_manager: self.admin.key(),
             treasury,
             protocol_fee_bps,
+            max_creator_fee_bps: 0,
+            referral_fee_bps: 0,
+            scoring_authority: Pubkey::default(),
+            timelock_delay_secs,
+            emergency_timeout_secs: DEFAULT_EMERGENCY_TIMEOUT_SECS,
+            claim_window_secs: DEFAULT_CLAIM_WINDOW_SECS,
+            pending_update: None,
             market_count: 0,
-            paused: false,
+            pause_flags: 0,
             bump: bumps.protocol,
         });
 
-        Ok(())
+        Ok(ProtocolInitialized {
+            admin_authority: self.protocol.admin_authority,
+            treasury: self.protocol.treasury,
+            protocol_fee_bps: self.protocol.protocol_fee_bps,
+            timelock_delay_secs,
+            emergency_timeout_secs: DEFAULT_EMERGENCY_TIMEOUT_SECS,
+            claim_window_secs: DEFAULT_CLAIM_WINDOW_SECS,
+        })
     }
 }
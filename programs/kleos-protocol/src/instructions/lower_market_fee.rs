@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::MarketFeeLowered, Market, MarketStatus, Protocol};
+
+/// Override the fee snapshotted on a market. The fee can only go down, so stakers never pay
+/// more than what was in effect when the market was created.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LowerMarketFee<'info> {
+    #[account(mut)]
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = fee_manager @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> LowerMarketFee<'info> {
+    pub fn lower_market_fee(&mut self, fee_bps: u16) -> Result<MarketFeeLowered> {
+        // Fee is locked in once settlement has taken it
+        require!(
+            !matches!(
+                self.market.status,
+                MarketStatus::Settled | MarketStatus::Cancelled
+            ),
+            ProtocolError::InvalidMarketState
+        );
+
+        require!(
+            fee_bps <= self.market.fee_bps,
+            ProtocolError::FeeIncreaseNotAllowed
+        );
+
+        let previous_fee_bps = self.market.fee_bps;
+        self.market.fee_bps = fee_bps;
+
+        Ok(MarketFeeLowered {
+            market: self.market.key(),
+            previous_fee_bps,
+            fee_bps,
+        })
+    }
+}
@@ -1,5 +1,7 @@
 pub mod initialize_protocol;
-pub mod update_protocol;
+pub mod queue_protocol_update;
+pub mod execute_protocol_update;
+pub mod cancel_protocol_update;
 pub mod create_market;
 pub mod create_market_native;
 pub mod edit_market;
@@ -7,13 +9,50 @@ pub mod open_market;
 pub mod place_position;
 pub mod place_position_native;
 pub mod close_market;
+pub mod resolve_market;
 pub mod settle_market;
 pub mod settle_market_native;
 pub mod claim_payout;
 pub mod claim_payout_native;
+pub mod cancel_market;
+pub mod refund_position;
+pub mod refund_position_native;
+pub mod close_position;
+pub mod close_attestation_receipt;
+pub mod close_settled_market;
+pub mod close_settled_market_native;
+pub mod sweep_market;
+pub mod sweep_market_native;
+pub mod lower_market_fee;
+pub mod create_mint_config;
+pub mod update_mint_config;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_proposal;
+pub mod set_pause_flags;
+pub mod freeze_market;
+pub mod unfreeze_market;
+pub mod emergency_withdraw;
+pub mod emergency_withdraw_native;
+pub mod close_empty_market;
+pub mod close_empty_market_native;
+pub mod release_creator_bond;
+pub mod release_creator_bond_native;
+pub mod slash_creator_bond;
+pub mod slash_creator_bond_native;
+pub mod create_referrer_account;
+pub mod create_referrer_account_native;
+pub mod collect_referral_reward;
+pub mod collect_referral_reward_native;
+pub mod withdraw_referral_rewards;
+pub mod withdraw_referral_rewards_native;
+pub mod grant_role;
+pub mod revoke_role;
 
 pub use initialize_protocol::*;
-pub use update_protocol::*;
+pub use queue_protocol_update::*;
+pub use execute_protocol_update::*;
+pub use cancel_protocol_update::*;
 pub use create_market::*;
 pub use create_market_native::*;
 pub use edit_market::*;
@@ -21,7 +60,42 @@ pub use open_market::*;
 pub use place_position::*;
 pub use place_position_native::*;
 pub use close_market::*;
+pub use resolve_market::*;
 pub use settle_market::*;
 pub use settle_market_native::*;
 pub use claim_payout::*;
 pub use claim_payout_native::*;
+pub use cancel_market::*;
+pub use refund_position::*;
+pub use refund_position_native::*;
+pub use close_position::*;
+pub use close_attestation_receipt::*;
+pub use close_settled_market::*;
+pub use close_settled_market_native::*;
+pub use sweep_market::*;
+pub use sweep_market_native::*;
+pub use lower_market_fee::*;
+pub use create_mint_config::*;
+pub use update_mint_config::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use cancel_admin_proposal::*;
+pub use set_pause_flags::*;
+pub use freeze_market::*;
+pub use unfreeze_market::*;
+pub use emergency_withdraw::*;
+pub use emergency_withdraw_native::*;
+pub use close_empty_market::*;
+pub use close_empty_market_native::*;
+pub use release_creator_bond::*;
+pub use release_creator_bond_native::*;
+pub use slash_creator_bond::*;
+pub use slash_creator_bond_native::*;
+pub use create_referrer_account::*;
+pub use create_referrer_account_native::*;
+pub use collect_referral_reward::*;
+pub use collect_referral_reward_native::*;
+pub use withdraw_referral_rewards::*;
+pub use withdraw_referral_rewards_native::*;
+pub use grant_role::*;
+pub use revoke_role::*;
@@ -1,16 +1,20 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ProtocolError, Market, MarketStatus, Protocol};
+use crate::{
+    errors::ProtocolError, events::MarketOpened, Market, MarketStatus, Protocol,
+    PAUSE_MARKET_CREATION,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct OpenMarket<'info> {
     #[account(mut)]
-    pub admin_authority: Signer<'info>,
+    pub market_operator: Signer<'info>,
 
     #[account(
         seeds = [b"protocol"],
         bump = protocol.bump,
-        has_one = admin_authority @ ProtocolError::Unauthorized
+        has_one = market_operator @ ProtocolError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
@@ -19,9 +23,9 @@ pub struct OpenMarket<'info> {
 }
 
 impl<'info> OpenMarket<'info> {
-    pub fn open_market(&mut self) -> Result<()> {
-        // Protocol must not be paused
-        require!(!self.protocol.paused, ProtocolError::ProtocolPaused);
+    pub fn open_market(&mut self) -> Result<MarketOpened> {
+        // Market creation must not be paused
+        self.protocol.require_unpaused(PAUSE_MARKET_CREATION)?;
 
         // Market must be Draft
         require!(
@@ -40,6 +44,10 @@ impl<'info> OpenMarket<'info> {
         // Transition state
         self.market.status = MarketStatus::Open;
 
-        Ok(())
+        Ok(MarketOpened {
+            market: self.market.key(),
+            start_ts: self.market.start_ts,
+            end_ts: self.market.end_ts,
+        })
     }
 }
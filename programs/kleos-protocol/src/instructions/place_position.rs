@@ -1,11 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
-    constants::MAX_MULTIPLIER, errors::ProtocolError, Market, MarketStatus, Position, Protocol,
+    attestation::Attestation, errors::ProtocolError, events::PositionPlaced, math::mul_div, Market,
+    MarketReferral, MarketStatus, MintConfig, MultiplierSchedule, Position, Protocol,
+    ReferrerAccount, PAUSE_DEPOSITS,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(selected_item_index: u8)]
 pub struct PlacePosition<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -20,27 +25,65 @@ pub struct PlacePosition<'info> {
     pub market: Account<'info, Market>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = user,
         space = 8 + Position::INIT_SPACE,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[selected_item_index],
+        ],
         bump
     )]
     pub position: Account<'info, Position>,
 
+    #[account(
+        seeds = [b"mint_config", market.token_mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
     #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
       mut,
       constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Instructions sysvar; read for the scoring-authority Ed25519 attestation
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Single-use receipt PDA for the attestation; required on attested markets,
+    /// validated and created in the handler
+    #[account(mut)]
+    pub attestation_receipt: Option<UncheckedAccount<'info>>,
+
+    /// Referrer's account for the market mint; the position earns referral rewards for it.
+    /// Required on every deposit into a referred position
+    pub referrer_account: Option<Account<'info, ReferrerAccount>>,
+
+    /// Referral stake of `referrer_account` in this market; required with it
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + MarketReferral::INIT_SPACE,
+        seeds = [
+            b"referral",
+            market.key().as_ref(),
+            ReferrerAccount::seed(&referrer_account),
+        ],
+        bump
+    )]
+    pub market_referral: Option<Account<'info, MarketReferral>>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
@@ -49,11 +92,14 @@ impl<'info> PlacePosition<'info> {
         &mut self,
         selected_item_index: u8,
         raw_stake: u64,
-        effective_stake: u128,
-        bumps: PlacePositionBumps,
-    ) -> Result<()> {
-        // Protocol must not be paused
-        require!(!self.protocol.paused, ProtocolError::ProtocolPaused);
+        min_effective_stake: u128,
+        bumps: &PlacePositionBumps,
+    ) -> Result<PositionPlaced> {
+        // Deposits must not be paused
+        self.protocol.require_unpaused(PAUSE_DEPOSITS)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
 
         // Must be SPL token market
         require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
@@ -75,17 +121,21 @@ impl<'info> PlacePosition<'info> {
         // Validate stake
         require!(raw_stake > 0, ProtocolError::InvalidStakeAmount);
 
+        // Mint must still be enabled and the stake within its bounds
+        self.mint_config.check_stake(raw_stake)?;
+
         // Validate item index
         require!(
             selected_item_index < self.market.item_count,
             ProtocolError::InvalidItemIndex
         );
 
-        // Enforce effective stake cap
-        require!(
-            effective_stake <= raw_stake as u128 * MAX_MULTIPLIER,
-            ProtocolError::EffectiveStakeTooLarge
-        );
+        // Referrer account must be for this mint and not the user's own
+        let referrer = ReferrerAccount::referrer_for(
+            self.referrer_account.as_deref(),
+            &self.user.key(),
+            &self.market.token_mint,
+        )?;
 
         // Validate correct mint
         require!(
@@ -103,38 +153,113 @@ impl<'info> PlacePosition<'info> {
             ProtocolError::InvalidStakeAmount
         );
 
-        require!(effective_stake > 0, ProtocolError::InvalidStakeAmount);
-
         // Transfer tokens to vault
+        let vault_balance_before = self.vault.amount;
+
         let cpi_ctx = CpiContext::new(
             self.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: self.user_token_account.to_account_info(),
+                mint: self.token_mint.to_account_info(),
                 to: self.vault.to_account_info(),
                 authority: self.user.to_account_info(),
             },
         );
 
-        anchor_spl::token::transfer(cpi_ctx, raw_stake)?;
-
-        // Store position
-        self.position.set_inner(Position {
-            market: self.market.key(),
-            user: self.user.key(),
-            selected_item_index,
+        anchor_spl::token_interface::transfer_checked(
+            cpi_ctx,
             raw_stake,
-            effective_stake,
-            claimed: false,
-            bump: bumps.position,
-        });
+            self.token_mint.decimals,
+        )?;
+
+        // Credit what actually arrived; transfer-fee mints withhold part of the amount
+        self.vault.reload()?;
+
+        let credited_stake = self
+            .vault
+            .amount
+            .checked_sub(vault_balance_before)
+            .ok_or(ProtocolError::MathOverflow)?;
+
+        require!(credited_stake > 0, ProtocolError::InvalidStakeAmount);
+
+        // Derive effective stake from the market's multiplier schedule, or take it from
+        // the scoring authority's attestation
+        let effective_stake = match self.market.multiplier_schedule {
+            MultiplierSchedule::Attested => {
+                // The attestation covers the requested stake; scale it to what was credited
+                let attested_stake =
+                    self.consume_attestation(selected_item_index, raw_stake, current_time)?;
+
+                mul_div(attested_stake, credited_stake as u128, raw_stake as u128)?
+            }
+            _ => self.market.multiplier_schedule.effective_stake(
+                credited_stake,
+                current_time,
+                self.market.start_ts,
+                self.market.end_ts,
+            )?,
+        };
+
+        // Respect the caller's minimum
+        require!(
+            effective_stake >= min_effective_stake,
+            ProtocolError::EffectiveStakeBelowMinimum
+        );
+
+        require!(effective_stake > 0, ProtocolError::InvalidStakeAmount);
+
+        // Top up the position already held on this item, or open a new one
+        let top_up = self.position.is_open();
+
+        if top_up {
+            self.position.top_up(credited_stake, effective_stake, referrer)?;
+        } else {
+            self.position.set_inner(Position {
+                market: self.market.key(),
+                user: self.user.key(),
+                selected_item_index,
+                raw_stake: credited_stake,
+                effective_stake,
+                claimed: false,
+                refunded: false,
+                referrer,
+                bump: bumps.position,
+            });
+
+            self.market.position_count = self
+                .market
+                .position_count
+                .checked_add(1)
+                .ok_or(ProtocolError::MathOverflow)?;
+        }
+
+        // The whole position must stay within the mint's stake bounds
+        self.mint_config.check_stake(self.position.raw_stake)?;
+
+        let referrer = self.position.referrer;
 
         // Update market totals
+
         self.market.total_raw_stake = self
             .market
             .total_raw_stake
-            .checked_add(raw_stake)
+            .checked_add(credited_stake)
             .ok_or(ProtocolError::MathOverflow)?;
 
+        // Referred stake also counts towards the referrer's reward in this market
+        if referrer.is_some() {
+            self.market.referred_raw_stake = self
+                .market
+                .referred_raw_stake
+                .checked_add(credited_stake)
+                .ok_or(ProtocolError::MathOverflow)?;
+
+            self.record_referral(credited_stake, bumps.market_referral)?;
+        } else {
+            require!(self.market_referral.is_none(), ProtocolError::InvalidReferrer);
+        }
+
         self.market.total_effective_stake = self
             .market
             .total_effective_stake
@@ -147,6 +272,92 @@ impl<'info> PlacePosition<'info> {
             .checked_add(effective_stake)
             .ok_or(ProtocolError::MathOverflow)?;
 
+        Ok(PositionPlaced {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index,
+            raw_stake: credited_stake,
+            effective_stake,
+            top_up,
+            position_raw_stake: self.position.raw_stake,
+            referrer,
+            total_raw_stake: self.market.total_raw_stake,
+            total_effective_stake: self.market.total_effective_stake,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
+    }
+
+    /// Add a referred deposit to the referrer's `MarketReferral`, opening it on the first one.
+    fn record_referral(&mut self, raw_stake: u64, bump: Option<u8>) -> Result<()> {
+        let referrer_account = self
+            .referrer_account
+            .as_ref()
+            .ok_or(ProtocolError::MissingReferrerAccount)?;
+
+        referrer_account.check_for(&self.position, &self.market.token_mint)?;
+
+        let market_referral = self
+            .market_referral
+            .as_mut()
+            .ok_or(ProtocolError::MissingReferrerAccount)?;
+
+        if !market_referral.is_open() {
+            market_referral.set_inner(MarketReferral {
+                market: self.market.key(),
+                referrer_account: referrer_account.key(),
+                referred_raw_stake: 0,
+                payer: self.user.key(),
+                bump: bump.ok_or(ProtocolError::MissingReferrerAccount)?,
+            });
+
+            self.market.referral_count = self
+                .market
+                .referral_count
+                .checked_add(1)
+                .ok_or(ProtocolError::MathOverflow)?;
+        }
+
+        market_referral.referred_raw_stake = market_referral
+            .referred_raw_stake
+            .checked_add(raw_stake)
+            .ok_or(ProtocolError::MathOverflow)?;
+
         Ok(())
     }
+
+    /// Verify the attestation preceding this instruction and burn it; returns the attested
+    /// effective stake.
+    fn consume_attestation(
+        &self,
+        selected_item_index: u8,
+        raw_stake: u64,
+        current_time: i64,
+    ) -> Result<u128> {
+        let attestation = Attestation::load(
+            &self.instructions.to_account_info(),
+            &self.protocol.scoring_authority,
+        )?;
+
+        let effective_stake = attestation.verify(
+            &self.market.key(),
+            &self.user.key(),
+            selected_item_index,
+            raw_stake,
+            current_time,
+        )?;
+
+        let receipt = self
+            .attestation_receipt
+            .as_ref()
+            .ok_or(ProtocolError::MissingAttestation)?;
+
+        attestation.consume(
+            &receipt.to_account_info(),
+            &self.user.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        Ok(effective_stake)
+    }
 }
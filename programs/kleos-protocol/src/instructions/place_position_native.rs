@@ -1,16 +1,26 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+
 use crate::{
-    constants::MAX_MULTIPLIER,
+    attestation::Attestation,
     errors::ProtocolError,
+    events::PositionPlaced,
     Market,
+    MarketReferral,
     MarketStatus,
+    MintConfig,
+    MultiplierSchedule,
     Position,
     Protocol,
+    ReferrerAccount,
+    PAUSE_DEPOSITS,
 };
 
 /// Place a position on a native-SOL market. Transfers lamports from user to vault PDA.
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(selected_item_index: u8)]
 pub struct PlacePositionNative<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -25,14 +35,25 @@ pub struct PlacePositionNative<'info> {
     pub market: Account<'info, Market>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = user,
         space = 8 + Position::INIT_SPACE,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[selected_item_index],
+        ],
         bump
     )]
     pub position: Account<'info, Position>,
 
+    #[account(
+        seeds = [b"mint_config", market.token_mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
     /// CHECK: PDA that holds lamports; validated by seeds
     #[account(
         mut,
@@ -41,6 +62,33 @@ pub struct PlacePositionNative<'info> {
     )]
     pub vault: UncheckedAccount<'info>,
 
+    /// CHECK: Instructions sysvar; read for the scoring-authority Ed25519 attestation
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Single-use receipt PDA for the attestation; required on attested markets,
+    /// validated and created in the handler
+    #[account(mut)]
+    pub attestation_receipt: Option<UncheckedAccount<'info>>,
+
+    /// Referrer's account for the market mint; the position earns referral rewards for it.
+    /// Required on every deposit into a referred position
+    pub referrer_account: Option<Account<'info, ReferrerAccount>>,
+
+    /// Referral stake of `referrer_account` in this market; required with it
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + MarketReferral::INIT_SPACE,
+        seeds = [
+            b"referral",
+            market.key().as_ref(),
+            ReferrerAccount::seed(&referrer_account),
+        ],
+        bump
+    )]
+    pub market_referral: Option<Account<'info, MarketReferral>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -49,10 +97,11 @@ impl<'info> PlacePositionNative<'info> {
         &mut self,
         selected_item_index: u8,
         raw_stake: u64,
-        effective_stake: u128,
-        bumps: PlacePositionNativeBumps,
-    ) -> Result<()> {
-        require!(!self.protocol.paused, ProtocolError::ProtocolPaused);
+        min_effective_stake: u128,
+        bumps: &PlacePositionNativeBumps,
+    ) -> Result<PositionPlaced> {
+        self.protocol.require_unpaused(PAUSE_DEPOSITS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
         require!(
             self.market.status == MarketStatus::Open,
             ProtocolError::InvalidMarketState
@@ -65,19 +114,37 @@ impl<'info> PlacePositionNative<'info> {
             ProtocolError::InvalidTimestamp
         );
         require!(raw_stake > 0, ProtocolError::InvalidStakeAmount);
+        self.mint_config.check_stake(raw_stake)?;
         require!(
             selected_item_index < self.market.item_count,
             ProtocolError::InvalidItemIndex
         );
+
+        let effective_stake = match self.market.multiplier_schedule {
+            MultiplierSchedule::Attested => {
+                self.consume_attestation(selected_item_index, raw_stake, current_time)?
+            }
+            _ => self.market.multiplier_schedule.effective_stake(
+                raw_stake,
+                current_time,
+                self.market.start_ts,
+                self.market.end_ts,
+            )?,
+        };
         require!(
-            effective_stake <= raw_stake as u128 * MAX_MULTIPLIER,
-            ProtocolError::EffectiveStakeTooLarge
+            effective_stake >= min_effective_stake,
+            ProtocolError::EffectiveStakeBelowMinimum
         );
         require!(effective_stake > 0, ProtocolError::InvalidStakeAmount);
         require!(
             self.vault.key() == self.market.vault,
             ProtocolError::InvalidStakeAmount
         );
+        let referrer = ReferrerAccount::referrer_for(
+            self.referrer_account.as_deref(),
+            &self.user.key(),
+            &self.market.token_mint,
+        )?;
 
         let cpi_ctx = CpiContext::new(
             self.system_program.to_account_info(),
@@ -88,15 +155,33 @@ impl<'info> PlacePositionNative<'info> {
         );
         anchor_lang::system_program::transfer(cpi_ctx, raw_stake)?;
 
-        self.position.set_inner(Position {
-            market: self.market.key(),
-            user: self.user.key(),
-            selected_item_index,
-            raw_stake,
-            effective_stake,
-            claimed: false,
-            bump: bumps.position,
-        });
+        let top_up = self.position.is_open();
+
+        if top_up {
+            self.position.top_up(raw_stake, effective_stake, referrer)?;
+        } else {
+            self.position.set_inner(Position {
+                market: self.market.key(),
+                user: self.user.key(),
+                selected_item_index,
+                raw_stake,
+                effective_stake,
+                claimed: false,
+                refunded: false,
+                referrer,
+                bump: bumps.position,
+            });
+
+            self.market.position_count = self
+                .market
+                .position_count
+                .checked_add(1)
+                .ok_or(ProtocolError::MathOverflow)?;
+        }
+
+        self.mint_config.check_stake(self.position.raw_stake)?;
+
+        let referrer = self.position.referrer;
 
         self.market.total_raw_stake = self
             .market
@@ -104,6 +189,17 @@ impl<'info> PlacePositionNative<'info> {
             .checked_add(raw_stake)
             .ok_or(ProtocolError::MathOverflow)?;
 
+        if referrer.is_some() {
+            self.market.referred_raw_stake = self
+                .market
+                .referred_raw_stake
+                .checked_add(raw_stake)
+                .ok_or(ProtocolError::MathOverflow)?;
+            self.record_referral(raw_stake, bumps.market_referral)?;
+        } else {
+            require!(self.market_referral.is_none(), ProtocolError::InvalidReferrer);
+        }
+
         self.market.total_effective_stake = self
             .market
             .total_effective_stake
@@ -115,6 +211,92 @@ impl<'info> PlacePositionNative<'info> {
             .checked_add(effective_stake)
             .ok_or(ProtocolError::MathOverflow)?;
 
+        Ok(PositionPlaced {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index,
+            raw_stake,
+            effective_stake,
+            top_up,
+            position_raw_stake: self.position.raw_stake,
+            referrer,
+            total_raw_stake: self.market.total_raw_stake,
+            total_effective_stake: self.market.total_effective_stake,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
+    }
+
+    /// Add a referred deposit to the referrer's `MarketReferral`, opening it on the first one.
+    fn record_referral(&mut self, raw_stake: u64, bump: Option<u8>) -> Result<()> {
+        let referrer_account = self
+            .referrer_account
+            .as_ref()
+            .ok_or(ProtocolError::MissingReferrerAccount)?;
+
+        referrer_account.check_for(&self.position, &self.market.token_mint)?;
+
+        let market_referral = self
+            .market_referral
+            .as_mut()
+            .ok_or(ProtocolError::MissingReferrerAccount)?;
+
+        if !market_referral.is_open() {
+            market_referral.set_inner(MarketReferral {
+                market: self.market.key(),
+                referrer_account: referrer_account.key(),
+                referred_raw_stake: 0,
+                payer: self.user.key(),
+                bump: bump.ok_or(ProtocolError::MissingReferrerAccount)?,
+            });
+
+            self.market.referral_count = self
+                .market
+                .referral_count
+                .checked_add(1)
+                .ok_or(ProtocolError::MathOverflow)?;
+        }
+
+        market_referral.referred_raw_stake = market_referral
+            .referred_raw_stake
+            .checked_add(raw_stake)
+            .ok_or(ProtocolError::MathOverflow)?;
+
         Ok(())
     }
+
+    /// Verify the attestation preceding this instruction and burn it; returns the attested
+    /// effective stake.
+    fn consume_attestation(
+        &self,
+        selected_item_index: u8,
+        raw_stake: u64,
+        current_time: i64,
+    ) -> Result<u128> {
+        let attestation = Attestation::load(
+            &self.instructions.to_account_info(),
+            &self.protocol.scoring_authority,
+        )?;
+
+        let effective_stake = attestation.verify(
+            &self.market.key(),
+            &self.user.key(),
+            selected_item_index,
+            raw_stake,
+            current_time,
+        )?;
+
+        let receipt = self
+            .attestation_receipt
+            .as_ref()
+            .ok_or(ProtocolError::MissingAttestation)?;
+
+        attestation.consume(
+            &receipt.to_account_info(),
+            &self.user.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+
+        Ok(effective_stake)
+    }
 }
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::AdminProposed, Protocol};
+
+/// Nominate a new admin. Control only moves once the nominee signs `accept_admin`, so a
+/// mistyped key can be replaced or cancelled instead of locking the protocol.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<AdminProposed> {
+        self.protocol.pending_admin = Some(new_admin);
+
+        Ok(AdminProposed {
+            admin_authority: self.protocol.admin_authority,
+            pending_admin: new_admin,
+        })
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::ProtocolUpdateQueued, PendingProtocolUpdate, Protocol,
+    ProtocolUpdateGroup, BPS_DENOMINATOR,
+};
+
+/// Stage new protocol parameters behind the timelock. `None` keeps the value from any update
+/// already queued, or the live value if nothing is queued. The fees belong to the fee manager;
+/// everything else belongs to the admin. Queueing restarts the timer only for the group of
+/// fields the caller changes, so a fee change never delays an admin change or vice versa.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QueueProtocolUpdate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> QueueProtocolUpdate<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn queue_protocol_update(
+        &mut self,
+        protocol_fee_bps: Option<u16>,
+        max_creator_fee_bps: Option<u16>,
+        referral_fee_bps: Option<u16>,
+        treasury: Option<Pubkey>,
+        scoring_authority: Option<Pubkey>,
+        timelock_delay_secs: Option<i64>,
+        emergency_timeout_secs: Option<i64>,
+        claim_window_secs: Option<i64>,
+    ) -> Result<ProtocolUpdateQueued> {
+        let authority = self.authority.key();
+        let protocol = &mut self.protocol;
+
+        let fee_change = protocol_fee_bps.is_some()
+            || max_creator_fee_bps.is_some()
+            || referral_fee_bps.is_some();
+        let admin_change = treasury.is_some()
+            || scoring_authority.is_some()
+            || timelock_delay_secs.is_some()
+            || emergency_timeout_secs.is_some()
+            || claim_window_secs.is_some();
+
+        require!(fee_change || admin_change, ProtocolError::EmptyProtocolUpdate);
+        if fee_change {
+            require_keys_eq!(
+                authority,
+                protocol.update_authority(ProtocolUpdateGroup::Fees),
+                ProtocolError::Unauthorized
+            );
+        }
+        if admin_change {
+            require_keys_eq!(
+                authority,
+                protocol.update_authority(ProtocolUpdateGroup::Admin),
+                ProtocolError::Unauthorized
+            );
+        }
+
+        // The current delay applies, so shortening it is itself subject to the old delay
+        let eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(protocol.timelock_delay_secs)
+            .ok_or(ProtocolError::MathOverflow)?;
+
+        let base = protocol.pending_update.clone().unwrap_or(PendingProtocolUpdate {
+            protocol_fee_bps: protocol.protocol_fee_bps,
+            max_creator_fee_bps: protocol.max_creator_fee_bps,
+            referral_fee_bps: protocol.referral_fee_bps,
+            treasury: protocol.treasury,
+            scoring_authority: protocol.scoring_authority,
+            timelock_delay_secs: protocol.timelock_delay_secs,
+            emergency_timeout_secs: protocol.emergency_timeout_secs,
+            claim_window_secs: protocol.claim_window_secs,
+            fee_eta: None,
+            admin_eta: None,
+        });
+
+        let update = PendingProtocolUpdate {
+            protocol_fee_bps: protocol_fee_bps.unwrap_or(base.protocol_fee_bps),
+            max_creator_fee_bps: max_creator_fee_bps.unwrap_or(base.max_creator_fee_bps),
+            referral_fee_bps: referral_fee_bps.unwrap_or(base.referral_fee_bps),
+            treasury: treasury.unwrap_or(base.treasury),
+            scoring_authority: scoring_authority.unwrap_or(base.scoring_authority),
+            timelock_delay_secs: timelock_delay_secs.unwrap_or(base.timelock_delay_secs),
+            emergency_timeout_secs: emergency_timeout_secs.unwrap_or(base.emergency_timeout_secs),
+            claim_window_secs: claim_window_secs.unwrap_or(base.claim_window_secs),
+            fee_eta: if fee_change { Some(eta) } else { base.fee_eta },
+            admin_eta: if admin_change { Some(eta) } else { base.admin_eta },
+        };
+
+        require!(
+            u64::from(update.protocol_fee_bps) <= BPS_DENOMINATOR,
+            ProtocolError::InvalidProtocolFeeBps
+        );
+        require!(
+            u64::from(update.max_creator_fee_bps) <= BPS_DENOMINATOR,
+            ProtocolError::InvalidCreatorFeeBps
+        );
+        require!(
+            u64::from(update.referral_fee_bps) <= BPS_DENOMINATOR,
+            ProtocolError::InvalidReferralFeeBps
+        );
+        Protocol::validate_timelock_delay(update.timelock_delay_secs)?;
+        Protocol::validate_emergency_timeout(update.emergency_timeout_secs)?;
+        Protocol::validate_claim_window(update.claim_window_secs)?;
+
+        protocol.pending_update = Some(update.clone());
+
+        Ok(ProtocolUpdateQueued {
+            queued_by: authority,
+            update,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    errors::ProtocolError, events::PositionRefunded, Market, MarketStatus, Position, Protocol,
+    PAUSE_CLAIMS,
+};
+
+/// Return the full raw stake of a position in a cancelled SPL token market. No fee is taken.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
+        bump = position.bump,
+        has_one = market,
+        has_one = user
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RefundPosition<'info> {
+    pub fn refund_position(&mut self, bumps: &RefundPositionBumps) -> Result<PositionRefunded> {
+        // Claims must not be paused
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        // Market must be cancelled
+        require!(
+            self.market.status == MarketStatus::Cancelled,
+            ProtocolError::InvalidMarketState
+        );
+
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        // Prevent double refund
+        require!(!self.position.refunded, ProtocolError::AlreadyRefunded);
+
+        require!(
+            self.user_token_account.mint == self.market.token_mint,
+            ProtocolError::InvalidStakeAmount
+        );
+
+        let refund = self.position.raw_stake;
+
+        // Mark refunded BEFORE transfer
+        self.position.refunded = true;
+        self.market.record_refund()?;
+
+        if refund > 0 {
+            let market_key = self.market.key();
+
+            let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+            let signer = &[seeds];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.user_token_account.to_account_info(),
+                    authority: self.vault_authority.to_account_info(),
+                },
+                signer,
+            );
+
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                refund,
+                self.token_mint.decimals,
+            )?;
+        }
+
+        Ok(PositionRefunded {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index: self.position.selected_item_index,
+            raw_stake: refund,
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::PositionRefunded, Market, MarketStatus, Position, Protocol,
+    PAUSE_CLAIMS,
+};
+
+/// Return the full raw stake (lamports) of a position in a cancelled native-SOL market.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundPositionNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &[position.selected_item_index],
+        ],
+        bump = position.bump,
+        has_one = market,
+        has_one = user
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundPositionNative<'info> {
+    pub fn refund_position_native(
+        &mut self,
+        bumps: &RefundPositionNativeBumps,
+    ) -> Result<PositionRefunded> {
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+        require!(
+            self.market.status == MarketStatus::Cancelled,
+            ProtocolError::InvalidMarketState
+        );
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+        require!(!self.position.refunded, ProtocolError::AlreadyRefunded);
+
+        let refund = self.position.raw_stake;
+
+        self.position.refunded = true;
+        self.market.record_refund()?;
+
+        if refund > 0 {
+            let market_key = self.market.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                market_key.as_ref(),
+                &[bumps.vault],
+            ]];
+
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                &self.vault.key(),
+                &self.user.key(),
+                refund,
+            );
+
+            anchor_lang::solana_program::program::invoke_signed(
+                &transfer_ix,
+                &[
+                    self.vault.to_account_info(),
+                    self.user.to_account_info(),
+                    self.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
+        Ok(PositionRefunded {
+            market: self.market.key(),
+            position: self.position.key(),
+            user: self.user.key(),
+            selected_item_index: self.position.selected_item_index,
+            raw_stake: refund,
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{errors::ProtocolError, events::CreatorBondReleased, Market, Protocol, PAUSE_CLAIMS};
+
+/// Return the creator bond of an SPL token market once it settled, once it ended with no
+/// positions, or once the emergency timeout has passed on a market that was cancelled or never
+/// opened. Permissionless; the bond always goes to the market creator.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseCreatorBond<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        has_one = creator
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Bond recipient; must match market.creator
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ReleaseCreatorBond<'info> {
+    pub fn release_creator_bond(
+        &mut self,
+        bumps: &ReleaseCreatorBondBumps,
+    ) -> Result<CreatorBondReleased> {
+        // Releasing is a payout, so it honours the claims pause and market freezes
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        let amount = self.market.creator_bond;
+
+        require!(amount > 0, ProtocolError::NoCreatorBond);
+
+        // Market must have settled, ended empty, or been cancelled or left unopened past the
+        // emergency timeout
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.market
+                .creator_bond_releasable(now, self.protocol.emergency_timeout_secs)?,
+            ProtocolError::CreatorBondNotReleasable
+        );
+
+        self.market.creator_bond = 0;
+
+        let market_key = self.market.key();
+
+        let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.token_mint.to_account_info(),
+                to: self.creator_token_account.to_account_info(),
+                authority: self.vault_authority.to_account_info(),
+            },
+            signer,
+        );
+
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, self.token_mint.decimals)?;
+
+        Ok(CreatorBondReleased {
+            market: self.market.key(),
+            creator: self.market.creator,
+            amount,
+        })
+    }
+}
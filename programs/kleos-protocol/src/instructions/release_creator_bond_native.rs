@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::CreatorBondReleased, Market, Protocol, PAUSE_CLAIMS};
+
+/// Native-SOL counterpart of `release_creator_bond`. Moves the bond from the vault PDA to the
+/// market creator.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseCreatorBondNative<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        has_one = creator
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Bond recipient; must match market.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReleaseCreatorBondNative<'info> {
+    pub fn release_creator_bond_native(
+        &mut self,
+        bumps: &ReleaseCreatorBondNativeBumps,
+    ) -> Result<CreatorBondReleased> {
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        let amount = self.market.creator_bond;
+        require!(amount > 0, ProtocolError::NoCreatorBond);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.market
+                .creator_bond_releasable(now, self.protocol.emergency_timeout_secs)?,
+            ProtocolError::CreatorBondNotReleasable
+        );
+
+        self.market.creator_bond = 0;
+
+        let market_key = self.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            market_key.as_ref(),
+            &[bumps.vault],
+        ]];
+
+        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+            &self.vault.key(),
+            &self.creator.key(),
+            amount,
+        );
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &[
+                self.vault.to_account_info(),
+                self.creator.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        Ok(CreatorBondReleased {
+            market: self.market.key(),
+            creator: self.market.creator,
+            amount,
+        })
+    }
+}
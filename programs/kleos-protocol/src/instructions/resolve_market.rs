@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::MarketResolved, Market, MarketStatus, Protocol, PAUSE_SETTLEMENT,
+};
+
+/// Record the winning item of a closed market. Settlement and claims pay out against it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(mut)]
+    pub market_operator: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = market_operator @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> ResolveMarket<'info> {
+    pub fn resolve_market(&mut self, winning_item_index: u8) -> Result<MarketResolved> {
+        // Settlement must not be paused
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT)?;
+
+        // Market must be Closed
+        require!(
+            self.market.status == MarketStatus::Closed,
+            ProtocolError::InvalidMarketState
+        );
+
+        // Validate item index
+        require!(
+            winning_item_index < self.market.item_count,
+            ProtocolError::InvalidItemIndex
+        );
+
+        // Transition state
+        self.market.winning_item_index = Some(winning_item_index);
+        self.market.status = MarketStatus::Resolved;
+
+        Ok(MarketResolved {
+            market: self.market.key(),
+            winning_item_index,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
+    }
+}
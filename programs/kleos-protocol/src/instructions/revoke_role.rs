@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::RoleRevoked, Protocol, Role};
+
+/// Leave a role unassigned. Instructions gated on it fail until it is granted again.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> RevokeRole<'info> {
+    pub fn revoke_role(&mut self, role: Role) -> Result<RoleRevoked> {
+        let authority = self.protocol.role(role);
+        require!(authority != Pubkey::default(), ProtocolError::RoleNotAssigned);
+
+        self.protocol.set_role(role, Pubkey::default());
+
+        Ok(RoleRevoked {
+            role,
+            authority,
+            revoked_by: self.admin_authority.key(),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::ProtocolPauseUpdated, Protocol, PAUSE_ALL};
+
+/// Replace the set of paused operations. Takes effect immediately, without the timelock.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = pauser @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+impl<'info> SetPauseFlags<'info> {
+    pub fn set_pause_flags(&mut self, pause_flags: u8) -> Result<ProtocolPauseUpdated> {
+        require!(pause_flags & !PAUSE_ALL == 0, ProtocolError::InvalidPauseFlags);
+
+        self.protocol.pause_flags = pause_flags;
+
+        Ok(ProtocolPauseUpdated {
+            pauser: self.pauser.key(),
+            pause_flags,
+        })
+    }
+}
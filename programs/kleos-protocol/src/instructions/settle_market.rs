@@ -1,16 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
-    constants::BPS_DENOMINATOR,
+    constants::PAUSE_SETTLEMENT,
     errors::ProtocolError,
+    events::MarketSettled,
     Market,
     MarketStatus,
     Protocol,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SettleMarket<'info> {
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     #[account(
@@ -19,7 +23,10 @@ pub struct SettleMarket<'info> {
     )]
     pub protocol: Account<'info, Protocol>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = creator
+    )]
     pub market: Account<'info, Market>,
 
     /// CHECK: PDA authority for vault
@@ -29,25 +36,62 @@ pub struct SettleMarket<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Must be the configured protocol treasury
+    #[account(address = protocol.treasury @ ProtocolError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Treasury ATA for the market mint; created by the settler if missing
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Receives the creator fee share; must match market.creator
+    pub creator: UncheckedAccount<'info>,
+
+    /// Creator ATA for the market mint; created by the settler if missing
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SettleMarket<'info> {
   pub fn settle_market(
       &mut self,
-      bumps: SettleMarketBumps,
-  ) -> Result<()> {
+      bumps: &SettleMarketBumps,
+  ) -> Result<MarketSettled> {
+
+      // Settlement must not be paused
+      self.protocol.require_unpaused(PAUSE_SETTLEMENT)?;
 
-      // Must be Closed
+      // Market must not be frozen
+      require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+      // Must be Resolved
       require!(
-          self.market.status == MarketStatus::Closed,
+          self.market.status == MarketStatus::Resolved,
           ProtocolError::InvalidMarketState
       );
 
@@ -60,51 +104,78 @@ impl<'info> SettleMarket<'info> {
           ProtocolError::InvalidStakeAmount
       );
 
-      // Compute protocol fee
-      let protocol_fee = self
-          .market
-          .total_raw_stake
-          .checked_mul(self.protocol.protocol_fee_bps as u64)
-          .ok_or(ProtocolError::MathOverflow)?
-          .checked_div(BPS_DENOMINATOR)
-          .ok_or(ProtocolError::MathOverflow)?;
+      // Split the fee between treasury and creator, holding back the referrers' share
+      let (protocol_fee, creator_fee, referral_reserve) = self.market.settlement_fees()?;
 
       let distributable_pool = self
           .market
           .total_raw_stake
           .checked_sub(protocol_fee)
+          .and_then(|pool| pool.checked_sub(creator_fee))
+          .and_then(|pool| pool.checked_sub(referral_reserve))
           .ok_or(ProtocolError::MathOverflow)?;
 
-      // Transfer fee to treasury
+      // Transfer fee shares
       if protocol_fee > 0 {
-          let market_key = self.market.key();
-
-          let seeds: &[&[u8]] = &[
-              b"vault",
-              market_key.as_ref(),
-              &[bumps.vault_authority],
-          ];
-
-          let signer = &[seeds];
-
-          let cpi_ctx = CpiContext::new_with_signer(
-              self.token_program.to_account_info(),
-              Transfer {
-                  from: self.vault.to_account_info(),
-                  to: self.treasury_token_account.to_account_info(),
-                  authority: self.vault_authority.to_account_info(),
-              },
-              signer,
-          );
-
-          anchor_spl::token::transfer(cpi_ctx, protocol_fee)?;
+          let treasury_token_account = self.treasury_token_account.to_account_info();
+          self.pay_from_vault(treasury_token_account, protocol_fee, bumps.vault_authority)?;
+      }
+
+      if creator_fee > 0 {
+          let creator_token_account = self.creator_token_account.to_account_info();
+          self.pay_from_vault(creator_token_account, creator_fee, bumps.vault_authority)?;
       }
 
       // Store results
+      let current_time = Clock::get()?.unix_timestamp;
+
+      self.market.claim_deadline_ts = current_time
+          .checked_add(self.protocol.claim_window_secs)
+          .ok_or(ProtocolError::MathOverflow)?;
       self.market.protocol_fee_amount = protocol_fee;
+      self.market.creator_fee_amount = creator_fee;
+      self.market.referral_reserve = referral_reserve;
       self.market.distributable_pool = distributable_pool;
       self.market.status = MarketStatus::Settled;
 
-      Ok(())
+      Ok(MarketSettled {
+          market: self.market.key(),
+          winning_item_index: self
+              .market
+              .winning_item_index
+              .ok_or(ProtocolError::MarketNotResolved)?,
+          total_raw_stake: self.market.total_raw_stake,
+          protocol_fee_amount: self.market.protocol_fee_amount,
+          creator_fee_amount: self.market.creator_fee_amount,
+          referral_reserve: self.market.referral_reserve,
+          distributable_pool: self.market.distributable_pool,
+          claim_deadline_ts: self.market.claim_deadline_ts,
+          effective_stake_per_item: self.market.effective_stake_per_item,
+      })
+  }
+
+  fn pay_from_vault(&self, to: AccountInfo<'info>, amount: u64, vault_bump: u8) -> Result<()> {
+      let market_key = self.market.key();
+
+      let seeds: &[&[u8]] = &[
+          b"vault",
+          market_key.as_ref(),
+          &[vault_bump],
+      ];
+
+      let signer = &[seeds];
+
+      let cpi_ctx = CpiContext::new_with_signer(
+          self.token_program.to_account_info(),
+          TransferChecked {
+              from: self.vault.to_account_info(),
+              mint: self.token_mint.to_account_info(),
+              to,
+              authority: self.vault_authority.to_account_info(),
+          },
+          signer,
+      );
+
+      anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, self.token_mint.decimals)
   }
 }
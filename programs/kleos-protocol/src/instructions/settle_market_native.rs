@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::BPS_DENOMINATOR,
+    constants::PAUSE_SETTLEMENT,
     errors::ProtocolError,
+    events::MarketSettled,
     Market,
     MarketStatus,
     Protocol,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SettleMarketNative<'info> {
     pub signer: Signer<'info>,
@@ -18,7 +20,10 @@ pub struct SettleMarketNative<'info> {
     )]
     pub protocol: Account<'info, Protocol>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = creator
+    )]
     pub market: Account<'info, Market>,
 
     /// CHECK: Vault PDA that holds lamports; validated by seeds
@@ -30,17 +35,29 @@ pub struct SettleMarketNative<'info> {
     pub vault: UncheckedAccount<'info>,
 
     /// Treasury wallet that receives protocol fee (SOL)
-    /// CHECK: Any account that can receive lamports
-    #[account(mut)]
+    /// CHECK: Must be the configured protocol treasury
+    #[account(
+        mut,
+        address = protocol.treasury @ ProtocolError::InvalidTreasury
+    )]
     pub treasury: UncheckedAccount<'info>,
 
+    /// CHECK: Receives the creator fee share; must match market.creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SettleMarketNative<'info> {
-    pub fn settle_market_native(&mut self, bumps: SettleMarketNativeBumps) -> Result<()> {
+    pub fn settle_market_native(
+        &mut self,
+        bumps: &SettleMarketNativeBumps,
+    ) -> Result<MarketSettled> {
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
         require!(
-            self.market.status == MarketStatus::Closed,
+            self.market.status == MarketStatus::Resolved,
             ProtocolError::InvalidMarketState
         );
         require!(
@@ -49,49 +66,74 @@ impl<'info> SettleMarketNative<'info> {
         );
         require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
 
-        let protocol_fee = self
-            .market
-            .total_raw_stake
-            .checked_mul(self.protocol.protocol_fee_bps as u64)
-            .ok_or(ProtocolError::MathOverflow)?
-            .checked_div(BPS_DENOMINATOR)
-            .ok_or(ProtocolError::MathOverflow)?;
+        let (protocol_fee, creator_fee, referral_reserve) = self.market.settlement_fees()?;
 
         let distributable_pool = self
             .market
             .total_raw_stake
             .checked_sub(protocol_fee)
+            .and_then(|pool| pool.checked_sub(creator_fee))
+            .and_then(|pool| pool.checked_sub(referral_reserve))
             .ok_or(ProtocolError::MathOverflow)?;
 
         if protocol_fee > 0 {
-            let market_key = self.market.key();
-            let signer_seeds: &[&[&[u8]]] = &[&[
-                b"vault",
-                market_key.as_ref(),
-                &[bumps.vault],
-            ]];
-
-            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-                &self.vault.key(),
-                &self.treasury.key(),
-                protocol_fee,
-            );
-
-            anchor_lang::solana_program::program::invoke_signed(
-                &transfer_ix,
-                &[
-                    self.vault.to_account_info(),
-                    self.treasury.to_account_info(),
-                    self.system_program.to_account_info(),
-                ],
-                signer_seeds,
-            )?;
+            self.pay_from_vault(self.treasury.to_account_info(), protocol_fee, bumps.vault)?;
+        }
+
+        if creator_fee > 0 {
+            self.pay_from_vault(self.creator.to_account_info(), creator_fee, bumps.vault)?;
         }
 
+        let current_time = Clock::get()?.unix_timestamp;
+
+        self.market.claim_deadline_ts = current_time
+            .checked_add(self.protocol.claim_window_secs)
+            .ok_or(ProtocolError::MathOverflow)?;
         self.market.protocol_fee_amount = protocol_fee;
+        self.market.creator_fee_amount = creator_fee;
+        self.market.referral_reserve = referral_reserve;
         self.market.distributable_pool = distributable_pool;
         self.market.status = MarketStatus::Settled;
 
-        Ok(())
+        Ok(MarketSettled {
+            market: self.market.key(),
+            winning_item_index: self
+                .market
+                .winning_item_index
+                .ok_or(ProtocolError::MarketNotResolved)?,
+            total_raw_stake: self.market.total_raw_stake,
+            protocol_fee_amount: self.market.protocol_fee_amount,
+            creator_fee_amount: self.market.creator_fee_amount,
+            referral_reserve: self.market.referral_reserve,
+            distributable_pool: self.market.distributable_pool,
+            claim_deadline_ts: self.market.claim_deadline_ts,
+            effective_stake_per_item: self.market.effective_stake_per_item,
+        })
+    }
+
+    fn pay_from_vault(&self, to: AccountInfo<'info>, amount: u64, vault_bump: u8) -> Result<()> {
+        let market_key = self.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            market_key.as_ref(),
+            &[vault_bump],
+        ]];
+
+        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+            &self.vault.key(),
+            to.key,
+            amount,
+        );
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &[
+                self.vault.to_account_info(),
+                to,
+                self.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )
+        .map_err(Into::into)
     }
 }
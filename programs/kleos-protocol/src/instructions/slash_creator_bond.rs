@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{errors::ProtocolError, events::CreatorBondSlashed, Market, Protocol};
+
+/// Send the creator bond of a spam or invalid SPL token market to the treasury. Admin only;
+/// the market must be cancelled first, and the bond can no longer be slashed once the
+/// emergency timeout has made it releasable.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SlashCreatorBond<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.token_mint,
+        associated_token::authority = protocol.treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SlashCreatorBond<'info> {
+    pub fn slash_creator_bond(
+        &mut self,
+        bumps: &SlashCreatorBondBumps,
+    ) -> Result<CreatorBondSlashed> {
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        let amount = self.market.creator_bond;
+
+        require!(amount > 0, ProtocolError::NoCreatorBond);
+
+        // Market must be cancelled and still within the emergency timeout
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.market
+                .creator_bond_slashable(now, self.protocol.emergency_timeout_secs)?,
+            ProtocolError::CreatorBondNotSlashable
+        );
+
+        self.market.creator_bond = 0;
+
+        let market_key = self.market.key();
+
+        let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.token_mint.to_account_info(),
+                to: self.treasury_token_account.to_account_info(),
+                authority: self.vault_authority.to_account_info(),
+            },
+            signer,
+        );
+
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, self.token_mint.decimals)?;
+
+        Ok(CreatorBondSlashed {
+            market: self.market.key(),
+            creator: self.market.creator,
+            treasury: self.protocol.treasury,
+            amount,
+            slashed_by: self.admin_authority.key(),
+        })
+    }
+}
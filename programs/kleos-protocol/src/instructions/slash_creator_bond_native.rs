@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::CreatorBondSlashed, Market, Protocol};
+
+/// Native-SOL counterpart of `slash_creator_bond`. Moves the bond from the vault PDA to the
+/// treasury.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SlashCreatorBondNative<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the configured protocol treasury
+    #[account(
+        mut,
+        address = protocol.treasury @ ProtocolError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SlashCreatorBondNative<'info> {
+    pub fn slash_creator_bond_native(
+        &mut self,
+        bumps: &SlashCreatorBondNativeBumps,
+    ) -> Result<CreatorBondSlashed> {
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        let amount = self.market.creator_bond;
+        require!(amount > 0, ProtocolError::NoCreatorBond);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.market
+                .creator_bond_slashable(now, self.protocol.emergency_timeout_secs)?,
+            ProtocolError::CreatorBondNotSlashable
+        );
+
+        self.market.creator_bond = 0;
+
+        let market_key = self.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            market_key.as_ref(),
+            &[bumps.vault],
+        ]];
+
+        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+            &self.vault.key(),
+            &self.treasury.key(),
+            amount,
+        );
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &[
+                self.vault.to_account_info(),
+                self.treasury.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        Ok(CreatorBondSlashed {
+            market: self.market.key(),
+            creator: self.market.creator,
+            treasury: self.protocol.treasury,
+            amount,
+            slashed_by: self.admin_authority.key(),
+        })
+    }
+}
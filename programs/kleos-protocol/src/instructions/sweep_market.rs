@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    errors::ProtocolError, events::MarketSwept, Market, MarketStatus, Protocol, PAUSE_CLAIMS,
+    PAUSE_SETTLEMENT,
+};
+
+/// Move unclaimed payouts and rounding dust from an SPL vault to the treasury once the
+/// claim deadline has passed. Permissionless.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepMarket<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    pub market: Account<'info, Market>,
+
+    /// CHECK: PDA authority for vault
+    #[account(
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+      mut,
+      constraint = vault.key() == market.vault @ ProtocolError::InvalidStakeAmount
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.token_mint,
+        associated_token::authority = protocol.treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == market.token_mint @ ProtocolError::InvalidStakeAmount)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SweepMarket<'info> {
+    pub fn sweep_market(&mut self, bumps: &SweepMarketBumps) -> Result<MarketSwept> {
+        // Sweeping ends the claim window, so it honours both the settlement and claims pauses
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT | PAUSE_CLAIMS)?;
+
+        // Market must not be frozen
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+
+        // Market must be Settled
+        require!(
+            self.market.status == MarketStatus::Settled,
+            ProtocolError::InvalidMarketState
+        );
+
+        // Must be SPL token market
+        require!(!self.market.is_native, ProtocolError::InvalidStakeAmount);
+
+        // Creator bond must be released or slashed before the vault is emptied
+        require!(
+            self.market.creator_bond == 0,
+            ProtocolError::CreatorBondOutstanding
+        );
+
+        // Claim window must be over
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            current_time > self.market.claim_deadline_ts,
+            ProtocolError::ClaimDeadlineNotReached
+        );
+
+        let remaining = self.vault.amount;
+
+        if remaining > 0 {
+            let market_key = self.market.key();
+
+            let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[bumps.vault_authority]];
+
+            let signer = &[seeds];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.treasury_token_account.to_account_info(),
+                    authority: self.vault_authority.to_account_info(),
+                },
+                signer,
+            );
+
+            anchor_spl::token_interface::transfer_checked(
+                cpi_ctx,
+                remaining,
+                self.token_mint.decimals,
+            )?;
+        }
+
+        Ok(MarketSwept {
+            market: self.market.key(),
+            treasury: self.protocol.treasury,
+            amount: remaining,
+        })
+    }
+}
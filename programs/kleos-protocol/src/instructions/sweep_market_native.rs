@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::MarketSwept, Market, MarketStatus, Protocol, PAUSE_CLAIMS,
+    PAUSE_SETTLEMENT,
+};
+
+/// Move unclaimed lamports from a native vault PDA to the treasury once the claim deadline
+/// has passed. Permissionless.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepMarketNative<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Vault PDA that holds lamports; validated by seeds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the configured protocol treasury
+    #[account(
+        mut,
+        address = protocol.treasury @ ProtocolError::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SweepMarketNative<'info> {
+    pub fn sweep_market_native(&mut self, bumps: &SweepMarketNativeBumps) -> Result<MarketSwept> {
+        self.protocol.require_unpaused(PAUSE_SETTLEMENT | PAUSE_CLAIMS)?;
+        require!(!self.market.frozen, ProtocolError::MarketFrozen);
+        require!(
+            self.market.status == MarketStatus::Settled,
+            ProtocolError::InvalidMarketState
+        );
+        require!(self.market.is_native, ProtocolError::InvalidStakeAmount);
+        require!(
+            self.market.creator_bond == 0,
+            ProtocolError::CreatorBondOutstanding
+        );
+
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            current_time > self.market.claim_deadline_ts,
+            ProtocolError::ClaimDeadlineNotReached
+        );
+
+        let remaining = self.vault.lamports();
+
+        if remaining > 0 {
+            let market_key = self.market.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault",
+                market_key.as_ref(),
+                &[bumps.vault],
+            ]];
+
+            let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+                &self.vault.key(),
+                &self.treasury.key(),
+                remaining,
+            );
+
+            anchor_lang::solana_program::program::invoke_signed(
+                &transfer_ix,
+                &[
+                    self.vault.to_account_info(),
+                    self.treasury.to_account_info(),
+                    self.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
+        Ok(MarketSwept {
+            market: self.market.key(),
+            treasury: self.protocol.treasury,
+            amount: remaining,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::MarketFreezeUpdated, Market, Protocol};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnfreezeMarket<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = pauser @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> UnfreezeMarket<'info> {
+    pub fn unfreeze_market(&mut self) -> Result<MarketFreezeUpdated> {
+        require!(self.market.frozen, ProtocolError::MarketNotFrozen);
+
+        self.market.frozen = false;
+
+        Ok(MarketFreezeUpdated {
+            market: self.market.key(),
+            frozen: false,
+            pauser: self.pauser.key(),
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ProtocolError, events::MintConfigUpdated, MintConfig, Protocol};
+
+/// Enable or disable a stake mint, or change its stake bounds and creator bond. Disabling a
+/// mint blocks new markets and new positions in it; existing positions can still settle and
+/// claim.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMintConfig<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+        has_one = admin_authority @ ProtocolError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+}
+
+impl<'info> UpdateMintConfig<'info> {
+    pub fn update_mint_config(
+        &mut self,
+        enabled: bool,
+        min_stake: u64,
+        max_stake: u64,
+        creator_bond: u64,
+    ) -> Result<MintConfigUpdated> {
+        MintConfig::validate_bounds(min_stake, max_stake)?;
+
+        self.mint_config.enabled = enabled;
+        self.mint_config.min_stake = min_stake;
+        self.mint_config.max_stake = max_stake;
+        self.mint_config.creator_bond = creator_bond;
+
+        Ok(MintConfigUpdated {
+            mint: self.mint_config.mint,
+            enabled,
+            min_stake,
+            max_stake,
+            creator_bond,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    errors::ProtocolError, events::ReferralRewardsWithdrawn, Protocol, ReferrerAccount,
+    PAUSE_CLAIMS,
+};
+
+/// Withdraw all accrued referral rewards in an SPL mint to the referrer's token account.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawReferralRewards<'info> {
+    pub referrer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.key().as_ref(), token_mint.key().as_ref()],
+        bump = referrer_account.bump,
+        has_one = referrer
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    #[account(
+      mut,
+      constraint = referrer_vault.key() == referrer_account.vault @ ProtocolError::InvalidReferrer
+    )]
+    pub referrer_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawReferralRewards<'info> {
+    pub fn withdraw_referral_rewards(&mut self) -> Result<ReferralRewardsWithdrawn> {
+        // Withdrawals are payouts, so they honour the claims pause
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+
+        let amount = self.referrer_account.accrued;
+
+        require!(amount > 0, ProtocolError::NoReferralRewards);
+
+        // Zero accrued BEFORE transfer
+        self.referrer_account.accrued = 0;
+
+        let referrer_key = self.referrer.key();
+        let mint_key = self.token_mint.key();
+
+        let seeds: &[&[u8]] = &[
+            b"referrer",
+            referrer_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.referrer_account.bump],
+        ];
+
+        let signer = &[seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.referrer_vault.to_account_info(),
+                mint: self.token_mint.to_account_info(),
+                to: self.referrer_token_account.to_account_info(),
+                authority: self.referrer_account.to_account_info(),
+            },
+            signer,
+        );
+
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, self.token_mint.decimals)?;
+
+        Ok(ReferralRewardsWithdrawn {
+            referrer_account: self.referrer_account.key(),
+            referrer: referrer_key,
+            mint: mint_key,
+            amount,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ProtocolError, events::ReferralRewardsWithdrawn, Protocol, ReferrerAccount,
+    PAUSE_CLAIMS,
+};
+
+/// Native-SOL counterpart of `withdraw_referral_rewards`. Moves accrued lamports off the
+/// referrer account, leaving its rent in place.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawReferralRewardsNative<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol.bump,
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.key().as_ref(), system_program.key().as_ref()],
+        bump = referrer_account.bump,
+        has_one = referrer
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawReferralRewardsNative<'info> {
+    pub fn withdraw_referral_rewards_native(&mut self) -> Result<ReferralRewardsWithdrawn> {
+        self.protocol.require_unpaused(PAUSE_CLAIMS)?;
+
+        let amount = self.referrer_account.accrued;
+        require!(amount > 0, ProtocolError::NoReferralRewards);
+
+        self.referrer_account.accrued = 0;
+
+        // Program-owned account: debit lamports directly
+        self.referrer_account.sub_lamports(amount)?;
+        self.referrer.add_lamports(amount)?;
+
+        Ok(ReferralRewardsWithdrawn {
+            referrer_account: self.referrer_account.key(),
+            referrer: self.referrer.key(),
+            mint: anchor_lang::system_program::ID,
+            amount,
+        })
+    }
+}
@@ -2,15 +2,21 @@ use anchor_lang::prelude::*;
 
 declare_id!("6jmg3EdNVE2PgLJHkzzGxG8aqsKWxLKvrgDjszTreAhD");
 
+pub mod attestation;
 pub mod constants;
 pub mod enums;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod math;
 pub mod states;
+pub mod token_extensions;
 
+pub use attestation::*;
 pub use constants::*;
 pub use enums::*;
 pub use errors::*;
+pub use events::*;
 pub use instructions::*;
 pub use states::*;
 
@@ -22,50 +28,113 @@ pub mod kleos_protocol {
         ctx: Context<InitializeProtocol>,
         protocol_fee_bps: u16,
         treasury: Pubkey,
+        timelock_delay_secs: i64,
     ) -> Result<()> {
-        ctx.accounts
-            .initialize_protocol(protocol_fee_bps, treasury, ctx.bumps)
+        let event = ctx.accounts.initialize_protocol(
+            protocol_fee_bps,
+            treasury,
+            timelock_delay_secs,
+            &ctx.bumps,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
-    pub fn update_protocol(
-        ctx: Context<UpdateProtocol>,
-        protocol_fee_bps: u16,
-        treasury: Pubkey,
-        paused: bool,
+    #[allow(clippy::too_many_arguments)]
+    pub fn queue_protocol_update(
+        ctx: Context<QueueProtocolUpdate>,
+        protocol_fee_bps: Option<u16>,
+        max_creator_fee_bps: Option<u16>,
+        referral_fee_bps: Option<u16>,
+        treasury: Option<Pubkey>,
+        scoring_authority: Option<Pubkey>,
+        timelock_delay_secs: Option<i64>,
+        emergency_timeout_secs: Option<i64>,
+        claim_window_secs: Option<i64>,
+    ) -> Result<()> {
+        let event = ctx.accounts.queue_protocol_update(
+            protocol_fee_bps,
+            max_creator_fee_bps,
+            referral_fee_bps,
+            treasury,
+            scoring_authority,
+            timelock_delay_secs,
+            emergency_timeout_secs,
+            claim_window_secs,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn execute_protocol_update(ctx: Context<ExecuteProtocolUpdate>) -> Result<()> {
+        let event = ctx.accounts.execute_protocol_update()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn cancel_protocol_update(
+        ctx: Context<CancelProtocolUpdate>,
+        group: ProtocolUpdateGroup,
     ) -> Result<()> {
-        ctx.accounts.update_protocol(protocol_fee_bps, treasury, paused)
+        let event = ctx.accounts.cancel_protocol_update(group)?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         ctx: Context<CreateMarket>,
         start_ts: i64,
         end_ts: i64,
         items_hash: [u8; 32],
         item_count: u8,
+        item_labels: Vec<String>,
+        multiplier_schedule: MultiplierSchedule,
+        creator_fee_bps: u16,
     ) -> Result<()> {
-        ctx.accounts.create_market(
+        let event = ctx.accounts.create_market(
             start_ts,
             end_ts,
             items_hash,
             item_count,
-            ctx.bumps,
-        )
+            item_labels,
+            multiplier_schedule,
+            creator_fee_bps,
+            &ctx.bumps,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market_native(
         ctx: Context<CreateMarketNative>,
         start_ts: i64,
         end_ts: i64,
         items_hash: [u8; 32],
         item_count: u8,
+        item_labels: Vec<String>,
+        multiplier_schedule: MultiplierSchedule,
+        creator_fee_bps: u16,
     ) -> Result<()> {
-        ctx.accounts.create_market_native(
+        let event = ctx.accounts.create_market_native(
             start_ts,
             end_ts,
             items_hash,
             item_count,
-            ctx.bumps,
-        )
+            item_labels,
+            multiplier_schedule,
+            creator_fee_bps,
+            &ctx.bumps,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn edit_market(
@@ -74,72 +143,394 @@ pub mod kleos_protocol {
         end_ts: i64,
         items_hash: [u8; 32],
         item_count: u8,
+        item_labels: Vec<String>,
+        multiplier_schedule: MultiplierSchedule,
     ) -> Result<()> {
-        ctx.accounts
-            .edit_market(start_ts, end_ts, items_hash, item_count)
+        let event = ctx.accounts.edit_market(
+            start_ts,
+            end_ts,
+            items_hash,
+            item_count,
+            item_labels,
+            multiplier_schedule,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn open_market(
         ctx: Context<OpenMarket>,
     ) -> Result<()> {
-        ctx.accounts.open_market()
+        let event = ctx.accounts.open_market()?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn place_position(
         ctx: Context<PlacePosition>,
         selected_item_index: u8,
         raw_stake: u64,
-        effective_stake: u128,
+        min_effective_stake: u128,
     ) -> Result<()> {
-        ctx.accounts.place_position(
+        let event = ctx.accounts.place_position(
             selected_item_index,
             raw_stake,
-            effective_stake,
-            ctx.bumps,
-        )
+            min_effective_stake,
+            &ctx.bumps,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn place_position_native(
         ctx: Context<PlacePositionNative>,
         selected_item_index: u8,
         raw_stake: u64,
-        effective_stake: u128,
+        min_effective_stake: u128,
     ) -> Result<()> {
-        ctx.accounts.place_position_native(
+        let event = ctx.accounts.place_position_native(
             selected_item_index,
             raw_stake,
-            effective_stake,
-            ctx.bumps,
-        )
+            min_effective_stake,
+            &ctx.bumps,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn close_market(
         ctx: Context<CloseMarket>,
     ) -> Result<()> {
-        ctx.accounts.close_market()
+        let event = ctx.accounts.close_market()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn resolve_market(
+        ctx: Context<ResolveMarket>,
+        winning_item_index: u8,
+    ) -> Result<()> {
+        let event = ctx.accounts.resolve_market(winning_item_index)?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn settle_market(
         ctx: Context<SettleMarket>,
     ) -> Result<()> {
-        ctx.accounts.settle_market(ctx.bumps)
+        let event = ctx.accounts.settle_market(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn settle_market_native(
         ctx: Context<SettleMarketNative>,
     ) -> Result<()> {
-        ctx.accounts.settle_market_native(ctx.bumps)
+        let event = ctx.accounts.settle_market_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn claim_payout(
         ctx: Context<ClaimPayout>,
+        close_position: bool,
     ) -> Result<()> {
-        ctx.accounts.claim_payout(ctx.bumps)
+        let event = ctx.accounts.claim_payout(close_position, &ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 
     pub fn claim_payout_native(
         ctx: Context<ClaimPayoutNative>,
+        close_position: bool,
+    ) -> Result<()> {
+        let event = ctx.accounts.claim_payout_native(close_position, &ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn cancel_market(
+        ctx: Context<CancelMarket>,
+    ) -> Result<()> {
+        let event = ctx.accounts.cancel_market()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn refund_position(
+        ctx: Context<RefundPosition>,
+    ) -> Result<()> {
+        let event = ctx.accounts.refund_position(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn refund_position_native(
+        ctx: Context<RefundPositionNative>,
+    ) -> Result<()> {
+        let event = ctx.accounts.refund_position_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        let event = ctx.accounts.close_position()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn close_attestation_receipt(ctx: Context<CloseAttestationReceipt>) -> Result<()> {
+        let event = ctx.accounts.close_attestation_receipt()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn close_settled_market(
+        ctx: Context<CloseSettledMarket>,
+    ) -> Result<()> {
+        let event = ctx.accounts.close_settled_market(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn close_settled_market_native(
+        ctx: Context<CloseSettledMarketNative>,
+    ) -> Result<()> {
+        let event = ctx.accounts.close_settled_market_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn sweep_market(
+        ctx: Context<SweepMarket>,
+    ) -> Result<()> {
+        let event = ctx.accounts.sweep_market(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn sweep_market_native(
+        ctx: Context<SweepMarketNative>,
+    ) -> Result<()> {
+        let event = ctx.accounts.sweep_market_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn lower_market_fee(
+        ctx: Context<LowerMarketFee>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        let event = ctx.accounts.lower_market_fee(fee_bps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn create_mint_config(
+        ctx: Context<CreateMintConfig>,
+        mint: Pubkey,
+        min_stake: u64,
+        max_stake: u64,
+        creator_bond: u64,
     ) -> Result<()> {
-        ctx.accounts.claim_payout_native(ctx.bumps)
+        let event = ctx.accounts.create_mint_config(
+            mint,
+            min_stake,
+            max_stake,
+            creator_bond,
+            &ctx.bumps,
+        )?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn update_mint_config(
+        ctx: Context<UpdateMintConfig>,
+        enabled: bool,
+        min_stake: u64,
+        max_stake: u64,
+        creator_bond: u64,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .update_mint_config(enabled, min_stake, max_stake, creator_bond)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        let event = ctx.accounts.propose_admin(new_admin)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let event = ctx.accounts.accept_admin()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn cancel_admin_proposal(ctx: Context<CancelAdminProposal>) -> Result<()> {
+        let event = ctx.accounts.cancel_admin_proposal()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        let event = ctx.accounts.set_pause_flags(pause_flags)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn freeze_market(ctx: Context<FreezeMarket>) -> Result<()> {
+        let event = ctx.accounts.freeze_market()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn unfreeze_market(ctx: Context<UnfreezeMarket>) -> Result<()> {
+        let event = ctx.accounts.unfreeze_market()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, authority: Pubkey) -> Result<()> {
+        let event = ctx.accounts.grant_role(role, authority)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        let event = ctx.accounts.revoke_role(role)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        let event = ctx.accounts.emergency_withdraw(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn emergency_withdraw_native(ctx: Context<EmergencyWithdrawNative>) -> Result<()> {
+        let event = ctx.accounts.emergency_withdraw_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn close_empty_market(ctx: Context<CloseEmptyMarket>) -> Result<()> {
+        let event = ctx.accounts.close_empty_market(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn close_empty_market_native(ctx: Context<CloseEmptyMarketNative>) -> Result<()> {
+        let event = ctx.accounts.close_empty_market_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+        let event = ctx.accounts.release_creator_bond(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn release_creator_bond_native(ctx: Context<ReleaseCreatorBondNative>) -> Result<()> {
+        let event = ctx.accounts.release_creator_bond_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn slash_creator_bond(ctx: Context<SlashCreatorBond>) -> Result<()> {
+        let event = ctx.accounts.slash_creator_bond(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn slash_creator_bond_native(ctx: Context<SlashCreatorBondNative>) -> Result<()> {
+        let event = ctx.accounts.slash_creator_bond_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn create_referrer_account(ctx: Context<CreateReferrerAccount>) -> Result<()> {
+        let event = ctx.accounts.create_referrer_account(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn create_referrer_account_native(ctx: Context<CreateReferrerAccountNative>) -> Result<()> {
+        let event = ctx.accounts.create_referrer_account_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn collect_referral_reward(ctx: Context<CollectReferralReward>) -> Result<()> {
+        let event = ctx.accounts.collect_referral_reward(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn collect_referral_reward_native(ctx: Context<CollectReferralRewardNative>) -> Result<()> {
+        let event = ctx.accounts.collect_referral_reward_native(&ctx.bumps)?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn withdraw_referral_rewards(ctx: Context<WithdrawReferralRewards>) -> Result<()> {
+        let event = ctx.accounts.withdraw_referral_rewards()?;
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    pub fn withdraw_referral_rewards_native(ctx: Context<WithdrawReferralRewardsNative>) -> Result<()> {
+        let event = ctx.accounts.withdraw_referral_rewards_native()?;
+        emit_cpi!(event);
+
+        Ok(())
     }
 }
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ProtocolError;
+
+pub use u256::U256;
+
+// Kept in its own module so the generated code doesn't see Anchor's `Result` alias
+#[allow(clippy::all)]
+mod u256 {
+    uint::construct_uint! {
+        /// 256-bit unsigned integer used as the intermediate for full-precision mul-div.
+        pub struct U256(4);
+    }
+}
+
+/// `a * b / denominator`, rounded down, computed with a 256-bit intermediate so the product
+/// never overflows. Fails if `denominator` is zero or the quotient does not fit in u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Result<u128> {
+    require!(denominator != 0, ProtocolError::MathOverflow);
+
+    let quotient = U256::from(a)
+        .checked_mul(U256::from(b))
+        .ok_or(ProtocolError::MathOverflow)?
+        / U256::from(denominator);
+
+    require!(quotient <= U256::from(u128::MAX), ProtocolError::MathOverflow);
+
+    Ok(quotient.as_u128())
+}
+
+/// [`mul_div`] for results that must fit in a u64 token amount.
+pub fn mul_div_u64(a: u128, b: u128, denominator: u128) -> Result<u64> {
+    let quotient = mul_div(a, b, denominator)?;
+
+    u64::try_from(quotient).map_err(|_| error!(ProtocolError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const U64_MAX: u128 = u64::MAX as u128;
+
+    #[test]
+    fn small_values() {
+        assert_eq!(mul_div(0, 0, 1).unwrap(), 0);
+        assert_eq!(mul_div(1, 1, 1).unwrap(), 1);
+        assert_eq!(mul_div(6, 7, 3).unwrap(), 14);
+        assert_eq!(mul_div(10, 3, 4).unwrap(), 7);
+    }
+
+    #[test]
+    fn rounds_down() {
+        assert_eq!(mul_div(1, 1, 2).unwrap(), 0);
+        assert_eq!(mul_div(2, 5, 3).unwrap(), 3);
+        assert_eq!(mul_div(U64_MAX, 1, 2).unwrap(), U64_MAX / 2);
+    }
+
+    #[test]
+    fn zero_denominator_fails() {
+        assert!(mul_div(1, 1, 0).is_err());
+        assert!(mul_div(0, 0, 0).is_err());
+        assert!(mul_div_u64(1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn zero_factor_is_zero() {
+        assert_eq!(mul_div(0, u128::MAX, 1).unwrap(), 0);
+        assert_eq!(mul_div(u128::MAX, 0, 1).unwrap(), 0);
+        assert_eq!(mul_div(0, u128::MAX, u128::MAX).unwrap(), 0);
+    }
+
+    #[test]
+    fn u128_extremes() {
+        // Product is (2^128 - 1)^2, far beyond u128
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, 1, 1).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, 2, 2).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, u128::MAX - 1, u128::MAX).unwrap(), u128::MAX - 1);
+        assert_eq!(mul_div(u128::MAX - 1, u128::MAX, u128::MAX).unwrap(), u128::MAX - 1);
+        assert_eq!(mul_div(u128::MAX, 1, u128::MAX).unwrap(), 1);
+    }
+
+    #[test]
+    fn quotient_above_u128_fails() {
+        assert!(mul_div(u128::MAX, 2, 1).is_err());
+        assert!(mul_div(u128::MAX, u128::MAX, 1).is_err());
+        assert!(mul_div(u128::MAX, u128::MAX, u128::MAX - 1).is_err());
+        assert!(mul_div(1 << 64, 1 << 64, 1).is_err());
+    }
+
+    #[test]
+    fn payout_at_u64_extremes() {
+        // Largest effective stake a single position can hold (u64::MAX raw at the 20x cap)
+        let max_effective = U64_MAX * 20;
+
+        // Sole winner takes the whole pool
+        assert_eq!(mul_div_u64(max_effective, U64_MAX, max_effective).unwrap(), u64::MAX);
+
+        // Two equal winners split it, rounding down
+        assert_eq!(
+            mul_div_u64(max_effective, U64_MAX, max_effective * 2).unwrap(),
+            u64::MAX / 2
+        );
+
+        // The product max_effective * u64::MAX overflows u128 on its own
+        assert!(max_effective.checked_mul(U64_MAX).is_none());
+    }
+
+    #[test]
+    fn u64_result_bounds() {
+        assert_eq!(mul_div_u64(U64_MAX, 1, 1).unwrap(), u64::MAX);
+        assert_eq!(mul_div_u64(U64_MAX, U64_MAX, U64_MAX).unwrap(), u64::MAX);
+        assert!(mul_div_u64(U64_MAX + 1, 1, 1).is_err());
+        assert!(mul_div_u64(U64_MAX, 2, 1).is_err());
+        assert!(mul_div_u64(u128::MAX, 1, 1).is_err());
+        assert_eq!(mul_div_u64(u128::MAX, U64_MAX, u128::MAX).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn fee_at_u64_extremes() {
+        assert_eq!(mul_div_u64(U64_MAX, 10_000, 10_000).unwrap(), u64::MAX);
+        assert_eq!(mul_div_u64(U64_MAX, 0, 10_000).unwrap(), 0);
+        assert_eq!(mul_div_u64(U64_MAX, 1, 10_000).unwrap(), u64::MAX / 10_000);
+        assert_eq!(
+            mul_div_u64(U64_MAX, 300, 10_000).unwrap(),
+            (U64_MAX * 300 / 10_000) as u64
+        );
+    }
+
+    #[test]
+    fn matches_native_arithmetic_when_it_fits() {
+        let samples = [
+            0u128,
+            1,
+            2,
+            3,
+            9_999,
+            10_000,
+            U64_MAX - 1,
+            U64_MAX,
+            U64_MAX + 1,
+            1 << 100,
+        ];
+
+        for &a in &samples {
+            for &b in &samples {
+                for &d in samples.iter().filter(|&&d| d != 0) {
+                    match a.checked_mul(b) {
+                        Some(product) => assert_eq!(mul_div(a, b, d).unwrap(), product / d),
+                        // Beyond u128: the quotient must reconstruct the product within one
+                        // denominator, or the call must fail because the quotient is too wide
+                        None => {
+                            let product = U256::from(a) * U256::from(b);
+                            match mul_div(a, b, d) {
+                                Ok(q) => {
+                                    let lower = U256::from(q) * U256::from(d);
+                                    assert!(lower <= product && product - lower < U256::from(d));
+                                }
+                                Err(_) => {
+                                    assert!(product / U256::from(d) > U256::from(u128::MAX))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
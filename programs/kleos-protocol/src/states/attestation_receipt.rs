@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Marks a scoring attestation as used; seeded by the hash of the signed message. Once the
+/// attestation has expired it can never be accepted again, so anyone may then close the
+/// receipt and return its rent to `payer`.
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationReceipt {
+  pub payer: Pubkey,
+  pub expiry_ts: i64,
+  pub bump: u8,
+}
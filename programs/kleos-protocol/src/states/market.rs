@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::{MAX_ITEMS, MarketStatus};
+use crate::{
+  errors::ProtocolError, math::mul_div_u64, BPS_DENOMINATOR, MAX_ITEMS, MAX_MULTIPLIER, MarketStatus,
+  Position,
+};
 
 #[account]
 #[derive(InitSpace)]
@@ -10,14 +13,623 @@ pub struct Market {
   pub start_ts: i64,
   pub end_ts: i64,
   pub status: MarketStatus,
+  /// Set by `freeze_market`; blocks deposits, closing, settlement, claims and sweeps on this
+  /// market.
+  pub frozen: bool,
   pub total_raw_stake: u64,
   pub total_effective_stake: u128,
   pub effective_stake_per_item: [u128; MAX_ITEMS],
+  /// Multiplier applied to raw stake by `place_position`, derived on-chain from the clock.
+  pub multiplier_schedule: MultiplierSchedule,
+  /// Protocol fee snapshotted at creation; settlement uses this, not `Protocol`.
+  pub fee_bps: u16,
+  /// Share of the settlement fee paid to `creator`, in basis points of the fee. Capped by
+  /// `protocol.max_creator_fee_bps` at creation.
+  pub creator_fee_bps: u16,
+  /// Fee sent to the treasury at settlement.
   pub protocol_fee_amount: u64,
+  /// Fee sent to the creator at settlement.
+  pub creator_fee_amount: u64,
+  /// Referrers' share of a referred position's part of the protocol fee, in basis points.
+  pub referral_fee_bps: u16,
+  /// Raw stake placed by positions that carry a referrer.
+  pub referred_raw_stake: u64,
+  /// Protocol fee held back in the vault at settlement for referrers.
+  pub referral_reserve: u64,
+  /// Part of `referral_reserve` already credited to referrers; never exceeds it.
+  pub referral_rewards_credited: u64,
+  /// `MarketReferral` accounts opened by placements and closed by `collect_referral_reward`;
+  /// the market itself can only be closed once every one is collected.
+  pub referral_count: u64,
+  pub referrals_collected: u64,
   pub distributable_pool: u64,
+  /// Item recorded by `resolve_market`; only positions on this item are paid out.
+  pub winning_item_index: Option<u8>,
+  pub position_count: u64,
+  pub positions_claimed: u64,
+  /// Positions refunded by `refund_position` or `emergency_withdraw` once the market was
+  /// cancelled.
+  pub positions_refunded: u64,
+  /// Position accounts closed by `claim_payout` or `close_position`; the market itself can only
+  /// be closed once every position is, so no position's rent is stranded.
+  pub positions_closed: u64,
+  /// Sum of all payouts made by `claim_payout`; never exceeds `distributable_pool`.
+  pub total_claimed: u64,
+  /// Winning-item effective stake already claimed; the claim that completes it takes the remainder.
+  pub claimed_winning_stake: u128,
+  /// Set at settlement; claims are rejected and the vault can be swept after this time.
+  pub claim_deadline_ts: i64,
   pub token_mint: Pubkey,
   pub vault: Pubkey,
+  /// Account that paid rent for the market (and vault); receives it back on close.
+  pub creator: Pubkey,
+  /// Bond posted by a creator other than the market operator, held in the vault on top of
+  /// stakes. Returned by `release_creator_bond` or sent to the treasury by `slash_creator_bond`.
+  pub creator_bond: u64,
   pub bump: u8,
   /// When true, market uses native SOL (lamports); vault is vault_authority PDA.
   pub is_native: bool,
-}
\ No newline at end of file
+}
+
+/// How `place_position` derives effective stake from raw stake.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub enum MultiplierSchedule {
+  /// Early-bird curve: the multiplier falls linearly from `start_multiplier_bps` at `start_ts`
+  /// to `end_multiplier_bps` at `end_ts`. Both are in basis points of raw stake (10_000 = 1x).
+  Linear {
+    start_multiplier_bps: u32,
+    end_multiplier_bps: u32,
+  },
+  /// Effective stake comes from off-chain scoring, signed by `protocol.scoring_authority`.
+  Attested,
+}
+
+impl MultiplierSchedule {
+  /// Multipliers must be between 1x and `MAX_MULTIPLIER` and must not rise over time.
+  pub fn validate(&self) -> Result<()> {
+    if let MultiplierSchedule::Linear { start_multiplier_bps, end_multiplier_bps } = *self {
+      let max_bps = MAX_MULTIPLIER * BPS_DENOMINATOR as u128;
+
+      require!(
+        end_multiplier_bps as u64 >= BPS_DENOMINATOR
+          && start_multiplier_bps >= end_multiplier_bps
+          && start_multiplier_bps as u128 <= max_bps,
+        ProtocolError::InvalidMultiplierSchedule
+      );
+    }
+
+    Ok(())
+  }
+
+  /// Multiplier in effect at `now` for a market running from `start_ts` to `end_ts`.
+  pub fn multiplier_bps_at(&self, now: i64, start_ts: i64, end_ts: i64) -> Result<u32> {
+    let MultiplierSchedule::Linear { start_multiplier_bps, end_multiplier_bps } = *self else {
+      return err!(ProtocolError::MissingAttestation);
+    };
+
+    if now <= start_ts {
+      return Ok(start_multiplier_bps);
+    }
+    if now >= end_ts {
+      return Ok(end_multiplier_bps);
+    }
+
+    let elapsed = (now - start_ts) as u128;
+    let duration = (end_ts - start_ts) as u128;
+    let span = (start_multiplier_bps - end_multiplier_bps) as u128;
+
+    let decay = span
+      .checked_mul(elapsed)
+      .ok_or(ProtocolError::MathOverflow)?
+      .checked_div(duration)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(start_multiplier_bps - decay as u32)
+  }
+
+  /// Effective stake for `raw_stake` placed at `now`.
+  pub fn effective_stake(&self, raw_stake: u64, now: i64, start_ts: i64, end_ts: i64) -> Result<u128> {
+    let multiplier_bps = self.multiplier_bps_at(now, start_ts, end_ts)?;
+
+    let effective_stake = (raw_stake as u128)
+      .checked_mul(multiplier_bps as u128)
+      .ok_or(ProtocolError::MathOverflow)?
+      .checked_div(BPS_DENOMINATOR as u128)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(effective_stake)
+  }
+}
+
+impl Market {
+  /// Effective stake placed on the winning item, or 0 if the market is unresolved.
+  pub fn winning_effective_stake(&self) -> u128 {
+    match self.winning_item_index {
+      Some(idx) => self.effective_stake_per_item[idx as usize],
+      None => 0,
+    }
+  }
+
+  /// Settlement fee as `(treasury share, creator share, referral reserve)`. No fee is taken
+  /// when nobody backed the winner. The creator share and the referral reserve round down and
+  /// the treasury takes the remainder, so the three always add up to the full fee.
+  pub fn settlement_fees(&self) -> Result<(u64, u64, u64)> {
+    if self.winning_effective_stake() == 0 {
+      return Ok((0, 0, 0));
+    }
+
+    let fee = mul_div_u64(
+      self.total_raw_stake as u128,
+      self.fee_bps as u128,
+      BPS_DENOMINATOR as u128,
+    )?;
+
+    let creator_fee = mul_div_u64(
+      fee as u128,
+      self.creator_fee_bps as u128,
+      BPS_DENOMINATOR as u128,
+    )?;
+
+    let protocol_fee = fee - creator_fee;
+
+    // Referred positions' part of the protocol fee, then the referrers' share of that
+    let referred_fee = mul_div_u64(
+      protocol_fee as u128,
+      self.referred_raw_stake as u128,
+      self.total_raw_stake as u128,
+    )?;
+
+    let referral_reserve = mul_div_u64(
+      referred_fee as u128,
+      self.referral_fee_bps as u128,
+      BPS_DENOMINATOR as u128,
+    )?;
+
+    Ok((protocol_fee - referral_reserve, creator_fee, referral_reserve))
+  }
+
+  /// Count the collection of a referrer's `MarketReferral` and return its reward: a share of
+  /// `referral_reserve` pro-rata by referred raw stake while claims are open on a settled
+  /// market, and nothing once the deadline has passed or the market was cancelled. Rounding
+  /// dust stays in the vault and is swept.
+  pub fn record_referral_collected(&mut self, referred_raw_stake: u64, now: i64) -> Result<u64> {
+    let reward = match self.status {
+      MarketStatus::Settled if now <= self.claim_deadline_ts => {
+        self.record_referral_reward(referred_raw_stake)?
+      }
+      MarketStatus::Settled | MarketStatus::Cancelled => 0,
+      _ => return err!(ProtocolError::InvalidMarketState),
+    };
+
+    self.referrals_collected = self
+      .referrals_collected
+      .checked_add(1)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(reward)
+  }
+
+  fn record_referral_reward(&mut self, referred_raw_stake: u64) -> Result<u64> {
+    if self.referral_reserve == 0 {
+      return Ok(0);
+    }
+
+    let reward = mul_div_u64(
+      referred_raw_stake as u128,
+      self.referral_reserve as u128,
+      self.referred_raw_stake as u128,
+    )?;
+
+    let credited = self
+      .referral_rewards_credited
+      .checked_add(reward)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    require!(
+      credited <= self.referral_reserve,
+      ProtocolError::PayoutExceedsPool
+    );
+
+    self.referral_rewards_credited = credited;
+
+    Ok(reward)
+  }
+
+  /// Payout owed to `position` once the market is settled.
+  ///
+  /// Positions on the winning item share `distributable_pool` pro-rata by effective stake.
+  /// If nobody staked on the winning item, settlement takes no fee and every position is
+  /// refunded its raw stake.
+  pub fn payout_for(&self, position: &Position) -> Result<u64> {
+    let winning_item_index = self
+      .winning_item_index
+      .ok_or(ProtocolError::MarketNotResolved)?;
+
+    let winning_stake = self.winning_effective_stake();
+
+    if winning_stake == 0 {
+      return Ok(position.raw_stake);
+    }
+
+    if position.selected_item_index != winning_item_index {
+      return Ok(0);
+    }
+
+    // Payout: (position.effective_stake / effective_stake_per_item[winner]) × distributable_pool
+    mul_div_u64(
+      position.effective_stake,
+      self.distributable_pool as u128,
+      winning_stake,
+    )
+  }
+
+  /// Count a claim by `position` and return the amount to pay.
+  ///
+  /// Pro-rata payouts round down, so the claim that completes the winning stake receives
+  /// whatever is left of `distributable_pool` instead; claims together pay out exactly the pool.
+  pub fn record_claim(&mut self, position: &Position) -> Result<u64> {
+    let mut payout = self.payout_for(position)?;
+
+    if self.winning_effective_stake() > 0
+      && Some(position.selected_item_index) == self.winning_item_index
+    {
+      self.claimed_winning_stake = self
+        .claimed_winning_stake
+        .checked_add(position.effective_stake)
+        .ok_or(ProtocolError::MathOverflow)?;
+
+      // Last winner: pay the exact remainder, including rounding dust
+      if self.claimed_winning_stake == self.winning_effective_stake() {
+        payout = self
+          .distributable_pool
+          .checked_sub(self.total_claimed)
+          .ok_or(ProtocolError::PayoutExceedsPool)?;
+      }
+    }
+
+    let total_claimed = self
+      .total_claimed
+      .checked_add(payout)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    // Invariant: claims never pay out more than the pool
+    require!(
+      total_claimed <= self.distributable_pool,
+      ProtocolError::PayoutExceedsPool
+    );
+
+    self.total_claimed = total_claimed;
+
+    self.positions_claimed = self
+      .positions_claimed
+      .checked_add(1)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(payout)
+  }
+
+  /// Whether the creator's bond can be returned: the market settled, it ended with no
+  /// positions, or it was cancelled or never opened and the emergency timeout has passed since
+  /// `end_ts`. Markets cancelled by emergency withdrawal are already past that point.
+  pub fn creator_bond_releasable(&self, now: i64, emergency_timeout_secs: i64) -> Result<bool> {
+    Ok(match self.status {
+      MarketStatus::Settled => true,
+      MarketStatus::Closed | MarketStatus::Resolved => self.position_count == 0,
+      MarketStatus::Cancelled | MarketStatus::Draft => {
+        self.emergency_timeout_passed(now, emergency_timeout_secs)?
+      }
+      MarketStatus::Open => false,
+    })
+  }
+
+  /// Whether the admin can slash the creator's bond: only once the market is cancelled, and
+  /// only until the bond becomes releasable, so a slash never races `release_creator_bond`.
+  pub fn creator_bond_slashable(&self, now: i64, emergency_timeout_secs: i64) -> Result<bool> {
+    Ok(
+      self.status == MarketStatus::Cancelled
+        && !self.emergency_timeout_passed(now, emergency_timeout_secs)?,
+    )
+  }
+
+  fn emergency_timeout_passed(&self, now: i64, emergency_timeout_secs: i64) -> Result<bool> {
+    let available_at = self
+      .end_ts
+      .checked_add(emergency_timeout_secs)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(now >= available_at)
+  }
+
+  /// Whether nothing is left for stakers: nobody joined, or the market was cancelled and every
+  /// position has been refunded.
+  pub fn is_drained(&self) -> bool {
+    match self.status {
+      MarketStatus::Cancelled => self.positions_refunded == self.position_count,
+      _ => self.position_count == 0 && self.total_raw_stake == 0,
+    }
+  }
+
+  /// Whether `position`'s account can be closed: it was refunded, or the market settled and
+  /// the position has either claimed or missed the claim deadline.
+  pub fn position_closable(&self, position: &Position, now: i64) -> bool {
+    position.refunded
+      || (self.status == MarketStatus::Settled
+        && (position.claimed || now > self.claim_deadline_ts))
+  }
+
+  /// Count a position account closed by `claim_payout` or `close_position`.
+  pub fn record_position_closed(&mut self) -> Result<()> {
+    self.positions_closed = self
+      .positions_closed
+      .checked_add(1)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(())
+  }
+
+  /// Count a position refunded from a cancelled market.
+  pub fn record_refund(&mut self) -> Result<()> {
+    self.positions_refunded = self
+      .positions_refunded
+      .checked_add(1)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(())
+  }
+
+  /// Check that positions can be withdrawn without settlement: `timeout_secs` must have passed
+  /// since `end_ts` and the market must never have settled. The first withdrawal cancels the
+  /// market so settlement can no longer run against a vault that has started paying out.
+  /// Returns true if this call cancelled the market.
+  pub fn begin_emergency_withdrawal(&mut self, timeout_secs: i64, now: i64) -> Result<bool> {
+    require!(
+      self.emergency_timeout_passed(now, timeout_secs)?,
+      ProtocolError::EmergencyWithdrawalNotAvailable
+    );
+
+    match self.status {
+      MarketStatus::Open | MarketStatus::Closed | MarketStatus::Resolved => {
+        self.status = MarketStatus::Cancelled;
+        Ok(true)
+      }
+      MarketStatus::Cancelled => Ok(false),
+      MarketStatus::Draft | MarketStatus::Settled => {
+        err!(ProtocolError::EmergencyWithdrawalNotAvailable)
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const LINEAR: MultiplierSchedule = MultiplierSchedule::Linear {
+    start_multiplier_bps: 30_000,
+    end_multiplier_bps: 10_000,
+  };
+
+  fn market(effective_stake_per_item: [u128; MAX_ITEMS], distributable_pool: u64) -> Market {
+    Market {
+      market_id: 0,
+      items_hash: [0; 32],
+      item_count: 2,
+      start_ts: 0,
+      end_ts: 0,
+      status: MarketStatus::Settled,
+      frozen: false,
+      total_raw_stake: 0,
+      total_effective_stake: effective_stake_per_item.iter().sum(),
+      effective_stake_per_item,
+      multiplier_schedule: LINEAR,
+      fee_bps: 0,
+      creator_fee_bps: 0,
+      protocol_fee_amount: 0,
+      creator_fee_amount: 0,
+      referral_fee_bps: 0,
+      referred_raw_stake: 0,
+      referral_reserve: 0,
+      referral_rewards_credited: 0,
+      referral_count: 0,
+      referrals_collected: 0,
+      distributable_pool,
+      winning_item_index: Some(0),
+      position_count: 0,
+      positions_claimed: 0,
+      positions_refunded: 0,
+      positions_closed: 0,
+      total_claimed: 0,
+      claimed_winning_stake: 0,
+      claim_deadline_ts: 0,
+      token_mint: Pubkey::default(),
+      vault: Pubkey::default(),
+      creator: Pubkey::default(),
+      creator_bond: 0,
+      bump: 0,
+      is_native: true,
+    }
+  }
+
+  fn position(selected_item_index: u8, raw_stake: u64, effective_stake: u128) -> Position {
+    Position {
+      market: Pubkey::default(),
+      user: Pubkey::default(),
+      selected_item_index,
+      raw_stake,
+      effective_stake,
+      claimed: false,
+      refunded: false,
+      referrer: None,
+      bump: 0,
+    }
+  }
+
+  fn stakes(winning: u128, losing: u128) -> [u128; MAX_ITEMS] {
+    let mut per_item = [0; MAX_ITEMS];
+    per_item[0] = winning;
+    per_item[1] = losing;
+    per_item
+  }
+
+  #[test]
+  fn multiplier_holds_outside_the_market_window() {
+    assert_eq!(LINEAR.multiplier_bps_at(-1, 100, 200).unwrap(), 30_000);
+    assert_eq!(LINEAR.multiplier_bps_at(100, 100, 200).unwrap(), 30_000);
+    assert_eq!(LINEAR.multiplier_bps_at(200, 100, 200).unwrap(), 10_000);
+    assert_eq!(LINEAR.multiplier_bps_at(i64::MAX, 100, 200).unwrap(), 10_000);
+  }
+
+  #[test]
+  fn multiplier_decays_linearly() {
+    assert_eq!(LINEAR.multiplier_bps_at(101, 100, 200).unwrap(), 29_800);
+    assert_eq!(LINEAR.multiplier_bps_at(150, 100, 200).unwrap(), 20_000);
+    assert_eq!(LINEAR.multiplier_bps_at(199, 100, 200).unwrap(), 10_200);
+  }
+
+  #[test]
+  fn multiplier_decay_rounds_down() {
+    // 20_000 * 1 / 3 = 6_666.67, so the multiplier keeps the fraction
+    assert_eq!(LINEAR.multiplier_bps_at(1, 0, 3).unwrap(), 23_334);
+    assert_eq!(LINEAR.multiplier_bps_at(2, 0, 3).unwrap(), 16_667);
+  }
+
+  #[test]
+  fn flat_and_attested_multipliers() {
+    let flat = MultiplierSchedule::Linear {
+      start_multiplier_bps: 10_000,
+      end_multiplier_bps: 10_000,
+    };
+    assert_eq!(flat.multiplier_bps_at(150, 100, 200).unwrap(), 10_000);
+    assert!(MultiplierSchedule::Attested.multiplier_bps_at(150, 100, 200).is_err());
+  }
+
+  #[test]
+  fn settlement_fees_add_up_to_the_fee() {
+    let mut m = market(stakes(1, 1), 0);
+    m.total_raw_stake = 1_000_001;
+    m.fee_bps = 300;
+    m.creator_fee_bps = 3_333;
+    m.referred_raw_stake = 333_333;
+    m.referral_fee_bps = 5_000;
+
+    // Fee of 30_000.03 rounds down; the creator and referral shares round down and the
+    // treasury keeps the dust
+    let (protocol_fee, creator_fee, referral_reserve) = m.settlement_fees().unwrap();
+    assert_eq!((protocol_fee, creator_fee, referral_reserve), (16_668, 9_999, 3_333));
+    assert_eq!(protocol_fee + creator_fee + referral_reserve, 30_000);
+  }
+
+  #[test]
+  fn settlement_fees_round_to_zero_on_small_stakes() {
+    let mut m = market(stakes(1, 1), 0);
+    m.total_raw_stake = 33;
+    m.fee_bps = 300;
+    m.creator_fee_bps = 5_000;
+
+    assert_eq!(m.settlement_fees().unwrap(), (0, 0, 0));
+  }
+
+  #[test]
+  fn no_settlement_fee_without_winners() {
+    let mut m = market(stakes(0, 1_000), 0);
+    m.total_raw_stake = 1_000;
+    m.fee_bps = 300;
+
+    assert_eq!(m.settlement_fees().unwrap(), (0, 0, 0));
+  }
+
+  #[test]
+  fn referral_rewards_are_collected_while_claims_are_open() {
+    let mut m = market(stakes(1, 1), 0);
+    m.claim_deadline_ts = 1_000;
+    m.referred_raw_stake = 300;
+    m.referral_reserve = 100;
+
+    // Pro-rata shares round down; the dust stays in the vault for the sweep
+    assert_eq!(m.record_referral_collected(100, 1_000).unwrap(), 33);
+    assert_eq!(m.record_referral_collected(100, 1_000).unwrap(), 33);
+    assert_eq!(m.record_referral_collected(100, 1_001).unwrap(), 0);
+    assert_eq!(m.referral_rewards_credited, 66);
+    assert_eq!(m.referrals_collected, 3);
+
+    m.status = MarketStatus::Cancelled;
+    assert_eq!(m.record_referral_collected(100, 0).unwrap(), 0);
+
+    m.status = MarketStatus::Resolved;
+    assert!(m.record_referral_collected(100, 0).is_err());
+    assert_eq!(m.referrals_collected, 4);
+  }
+
+  #[test]
+  fn last_winner_claims_the_remainder() {
+    let mut m = market(stakes(3, 5), 100);
+
+    assert_eq!(m.record_claim(&position(0, 1, 1)).unwrap(), 33);
+    assert_eq!(m.record_claim(&position(1, 5, 5)).unwrap(), 0);
+    assert_eq!(m.record_claim(&position(0, 1, 1)).unwrap(), 33);
+    assert_eq!(m.record_claim(&position(0, 1, 1)).unwrap(), 34);
+
+    assert_eq!(m.total_claimed, 100);
+    assert_eq!(m.claimed_winning_stake, 3);
+    assert_eq!(m.positions_claimed, 4);
+  }
+
+  #[test]
+  fn claims_cannot_exceed_the_pool() {
+    let mut m = market(stakes(2, 0), 100);
+    m.total_claimed = 60;
+
+    assert!(m.record_claim(&position(0, 1, 1)).is_err());
+    assert_eq!(m.total_claimed, 60);
+  }
+
+  #[test]
+  fn claims_refund_raw_stake_without_winners() {
+    let mut m = market(stakes(0, 30), 30);
+
+    assert_eq!(m.record_claim(&position(1, 10, 10)).unwrap(), 10);
+    assert_eq!(m.record_claim(&position(1, 20, 20)).unwrap(), 20);
+    assert_eq!(m.total_claimed, 30);
+    assert_eq!(m.claimed_winning_stake, 0);
+  }
+
+  #[test]
+  fn positions_close_once_they_cannot_pay_out() {
+    let mut m = market(stakes(1, 1), 100);
+    m.claim_deadline_ts = 1_000;
+    let mut p = position(0, 1, 1);
+
+    assert!(!m.position_closable(&p, 1_000));
+    assert!(m.position_closable(&p, 1_001));
+    p.claimed = true;
+    assert!(m.position_closable(&p, 0));
+
+    m.status = MarketStatus::Cancelled;
+    p.claimed = false;
+    assert!(!m.position_closable(&p, 1_001));
+    p.refunded = true;
+    assert!(m.position_closable(&p, 0));
+  }
+
+  #[test]
+  fn creator_bond_slash_window_ends_when_release_opens() {
+    let mut m = market(stakes(0, 0), 0);
+    m.end_ts = 100;
+
+    m.status = MarketStatus::Cancelled;
+    assert!(m.creator_bond_slashable(109, 10).unwrap());
+    assert!(!m.creator_bond_releasable(109, 10).unwrap());
+    assert!(!m.creator_bond_slashable(110, 10).unwrap());
+    assert!(m.creator_bond_releasable(110, 10).unwrap());
+
+    // A draft the operator never opened is returned, never slashed
+    m.status = MarketStatus::Draft;
+    assert!(!m.creator_bond_slashable(109, 10).unwrap());
+    assert!(!m.creator_bond_releasable(109, 10).unwrap());
+    assert!(m.creator_bond_releasable(110, 10).unwrap());
+
+    m.status = MarketStatus::Settled;
+    assert!(m.creator_bond_releasable(0, 10).unwrap());
+    assert!(!m.creator_bond_slashable(0, 10).unwrap());
+  }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ProtocolError, MAX_ITEMS, MAX_ITEM_LABEL_LEN};
+
+/// Item labels of a market, stored alongside it so anyone can check them against `items_hash`.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketItems {
+  pub market: Pubkey,
+  #[max_len(MAX_ITEMS, MAX_ITEM_LABEL_LEN)]
+  pub labels: Vec<String>,
+  pub bump: u8,
+}
+
+impl MarketItems {
+  /// Canonical items hash: blake3 over the Borsh encoding of the label list, i.e. a u32 LE
+  /// label count followed by, for each label, its u32 LE byte length and UTF-8 bytes.
+  pub fn hash_labels(labels: &[String]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&(labels.len() as u32).to_le_bytes());
+    for label in labels {
+      hasher.update(&(label.len() as u32).to_le_bytes());
+      hasher.update(label.as_bytes());
+    }
+    *hasher.finalize().as_bytes()
+  }
+
+  /// Reject labels that don't match the market's declared `item_count` and `items_hash`.
+  pub fn verify_labels(labels: &[String], item_count: u8, items_hash: &[u8; 32]) -> Result<()> {
+    require!(
+      labels.len() == item_count as usize,
+      ProtocolError::ItemCountMismatch
+    );
+
+    for label in labels {
+      require!(
+        !label.is_empty() && label.len() <= MAX_ITEM_LABEL_LEN,
+        ProtocolError::InvalidItemLabel
+      );
+    }
+
+    require!(
+      Self::hash_labels(labels) == *items_hash,
+      ProtocolError::ItemsHashMismatch
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Raw stake one referrer brought into one market; seeded by market and `referrer_account`.
+/// Placement adds to it, and once the market settles anyone can collect the referrer's share
+/// of `referral_reserve` into `referrer_account`, so rewards never wait on the referred
+/// positions claiming.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketReferral {
+  pub market: Pubkey,
+  pub referrer_account: Pubkey,
+  pub referred_raw_stake: u64,
+  /// Paid the rent for this account; receives it back on collection.
+  pub payer: Pubkey,
+  pub bump: u8,
+}
+
+impl MarketReferral {
+  /// False until the first referred deposit initializes the account.
+  pub fn is_open(&self) -> bool {
+    self.market != Pubkey::default()
+  }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProtocolError;
+
+/// Admin approval of a stake mint. `create_market` requires an enabled config for the market's
+/// mint and `place_position` enforces its stake bounds. Native SOL markets use the system
+/// program id as their mint.
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+  pub mint: Pubkey,
+  pub enabled: bool,
+  /// Inclusive bounds on the raw stake of a single position, in base units of the mint.
+  pub min_stake: u64,
+  pub max_stake: u64,
+  /// Bond, in base units of the mint, that wallets other than the market operator post to
+  /// create a market. Zero disables permissionless creation for this mint.
+  pub creator_bond: u64,
+  pub bump: u8,
+}
+
+impl MintConfig {
+  pub fn validate_bounds(min_stake: u64, max_stake: u64) -> Result<()> {
+    require!(
+      min_stake > 0 && min_stake <= max_stake,
+      ProtocolError::InvalidMintConfig
+    );
+
+    Ok(())
+  }
+
+  /// Bond owed by a market creator; the market operator creates markets without one.
+  pub fn creator_bond_for(&self, is_market_operator: bool) -> Result<u64> {
+    if is_market_operator {
+      return Ok(0);
+    }
+
+    require!(self.creator_bond > 0, ProtocolError::PermissionlessCreationDisabled);
+
+    Ok(self.creator_bond)
+  }
+
+  /// Reject a stake on a disabled mint or outside the configured bounds.
+  pub fn check_stake(&self, raw_stake: u64) -> Result<()> {
+    require!(self.enabled, ProtocolError::MintNotAllowed);
+    require!(raw_stake >= self.min_stake, ProtocolError::StakeBelowMinimum);
+    require!(raw_stake <= self.max_stake, ProtocolError::StakeAboveMaximum);
+
+    Ok(())
+  }
+}
@@ -1,7 +1,17 @@
 pub mod protocol;
 pub mod market;
 pub mod position;
+pub mod market_items;
+pub mod mint_config;
+pub mod referrer_account;
+pub mod attestation_receipt;
+pub mod market_referral;
 
 pub use protocol::*;
 pub use market::*;
-pub use position::*;
\ No newline at end of file
+pub use position::*;
+pub use market_items::*;
+pub use mint_config::*;
+pub use referrer_account::*;
+pub use attestation_receipt::*;
+pub use market_referral::*;
\ No newline at end of file
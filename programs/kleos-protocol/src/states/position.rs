@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::errors::ProtocolError;
 
+/// A user's stake on one item of a market; seeded by market, user and item index, so a user
+/// holds at most one position per item and further deposits on that item top it up.
 #[account]
 #[derive(InitSpace)]
 pub struct Position {
@@ -9,5 +12,40 @@ pub struct Position {
   pub raw_stake: u64,
   pub effective_stake: u128,
   pub claimed: bool,
+  pub refunded: bool,
+  /// Referrer whose `MarketReferral` this position's stake counts towards.
+  pub referrer: Option<Pubkey>,
   pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Position {
+  /// False until the first deposit initializes the account.
+  pub fn is_open(&self) -> bool {
+    self.market != Pubkey::default()
+  }
+
+  /// Add a deposit to an open position. A top-up may repeat the position's referrer but
+  /// cannot change it.
+  pub fn top_up(
+    &mut self,
+    raw_stake: u64,
+    effective_stake: u128,
+    referrer: Option<Pubkey>,
+  ) -> Result<()> {
+    require!(
+      referrer.is_none() || referrer == self.referrer,
+      ProtocolError::InvalidReferrer
+    );
+
+    self.raw_stake = self
+      .raw_stake
+      .checked_add(raw_stake)
+      .ok_or(ProtocolError::MathOverflow)?;
+    self.effective_stake = self
+      .effective_stake
+      .checked_add(effective_stake)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(())
+  }
+}
@@ -1,12 +1,157 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+  errors::ProtocolError, ProtocolUpdateGroup, Role, MAX_CLAIM_WINDOW_SECS,
+  MAX_EMERGENCY_TIMEOUT_SECS, MAX_TIMELOCK_DELAY_SECS,
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Protocol {
   pub admin_authority: Pubkey,
+  /// Proposed successor; becomes `admin_authority` once it signs `accept_admin`.
+  pub pending_admin: Option<Pubkey>,
+  /// Can pause and unpause the protocol and freeze individual markets, nothing else.
+  pub pauser: Pubkey,
+  /// Creates markets and moves them through their lifecycle.
+  pub market_operator: Pubkey,
+  /// Queues protocol, creator fee cap and referral fee changes and lowers per-market fees.
+  pub fee_manager: Pubkey,
   pub treasury: Pubkey,
   pub protocol_fee_bps: u16,
+  /// Upper bound on `Market.creator_fee_bps` for new markets.
+  pub max_creator_fee_bps: u16,
+  /// Share of a referred position's part of the protocol fee paid to its referrer, in basis
+  /// points. Snapshotted on new markets.
+  pub referral_fee_bps: u16,
+  /// Ed25519 key whose signatures attest effective stake on attested markets.
+  pub scoring_authority: Pubkey,
+  /// Minimum wait between `queue_protocol_update` and `execute_protocol_update`.
+  pub timelock_delay_secs: i64,
+  /// Time after a market's `end_ts` from which positions in an unsettled market can be
+  /// withdrawn with `emergency_withdraw`.
+  pub emergency_timeout_secs: i64,
+  /// How long winners have to claim after settlement before the vault can be swept.
+  /// Snapshotted into `Market.claim_deadline_ts` at settlement.
+  pub claim_window_secs: i64,
+  pub pending_update: Option<PendingProtocolUpdate>,
   pub market_count: u64,
-  pub paused: bool,
+  /// `PAUSE_*` bits for the operations currently halted.
+  pub pause_flags: u8,
   pub bump: u8
-}
\ No newline at end of file
+}
+
+/// Protocol parameters waiting out the timelock. Holds the full set of values, not just the
+/// ones that changed. The fee fields and the admin fields each have their own timer and are
+/// applied by `execute_protocol_update` once it has elapsed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct PendingProtocolUpdate {
+  pub protocol_fee_bps: u16,
+  pub max_creator_fee_bps: u16,
+  pub referral_fee_bps: u16,
+  pub treasury: Pubkey,
+  pub scoring_authority: Pubkey,
+  pub timelock_delay_secs: i64,
+  pub emergency_timeout_secs: i64,
+  pub claim_window_secs: i64,
+  /// Earliest time the fee fields can be applied; `None` if no fee change is queued.
+  pub fee_eta: Option<i64>,
+  /// Earliest time the treasury, scoring authority, timelock, emergency timeout and claim
+  /// window can be applied; `None` if none of them has a change queued.
+  pub admin_eta: Option<i64>,
+}
+
+impl Protocol {
+  pub fn require_unpaused(&self, flag: u8) -> Result<()> {
+    require!(self.pause_flags & flag == 0, ProtocolError::ProtocolPaused);
+
+    Ok(())
+  }
+
+  pub fn validate_timelock_delay(delay_secs: i64) -> Result<()> {
+    require!(
+      (0..=MAX_TIMELOCK_DELAY_SECS).contains(&delay_secs),
+      ProtocolError::InvalidTimelockDelay
+    );
+
+    Ok(())
+  }
+
+  pub fn validate_emergency_timeout(timeout_secs: i64) -> Result<()> {
+    require!(
+      (1..=MAX_EMERGENCY_TIMEOUT_SECS).contains(&timeout_secs),
+      ProtocolError::InvalidEmergencyTimeout
+    );
+
+    Ok(())
+  }
+
+  pub fn validate_claim_window(window_secs: i64) -> Result<()> {
+    require!(
+      (1..=MAX_CLAIM_WINDOW_SECS).contains(&window_secs),
+      ProtocolError::InvalidClaimWindow
+    );
+
+    Ok(())
+  }
+
+  /// Role that queues and cancels changes to `group`.
+  pub fn update_authority(&self, group: ProtocolUpdateGroup) -> Pubkey {
+    match group {
+      ProtocolUpdateGroup::Fees => self.fee_manager,
+      ProtocolUpdateGroup::Admin => self.admin_authority,
+    }
+  }
+
+  /// Drop the queued changes of one group. Its pending fields go back to the live values, so
+  /// a later queue that builds on the pending update does not pick them up again; the update
+  /// is cleared once neither group is queued.
+  pub fn cancel_pending_update(&mut self, group: ProtocolUpdateGroup) -> Result<()> {
+    let mut update = self
+      .pending_update
+      .clone()
+      .ok_or(ProtocolError::NoPendingUpdate)?;
+
+    match group {
+      ProtocolUpdateGroup::Fees => {
+        require!(update.fee_eta.is_some(), ProtocolError::NoPendingUpdate);
+
+        update.protocol_fee_bps = self.protocol_fee_bps;
+        update.max_creator_fee_bps = self.max_creator_fee_bps;
+        update.referral_fee_bps = self.referral_fee_bps;
+        update.fee_eta = None;
+      }
+      ProtocolUpdateGroup::Admin => {
+        require!(update.admin_eta.is_some(), ProtocolError::NoPendingUpdate);
+
+        update.treasury = self.treasury;
+        update.scoring_authority = self.scoring_authority;
+        update.timelock_delay_secs = self.timelock_delay_secs;
+        update.emergency_timeout_secs = self.emergency_timeout_secs;
+        update.claim_window_secs = self.claim_window_secs;
+        update.admin_eta = None;
+      }
+    }
+
+    self.pending_update =
+      (update.fee_eta.is_some() || update.admin_eta.is_some()).then_some(update);
+
+    Ok(())
+  }
+
+  pub fn role(&self, role: Role) -> Pubkey {
+    match role {
+      Role::Pauser => self.pauser,
+      Role::MarketOperator => self.market_operator,
+      Role::FeeManager => self.fee_manager,
+    }
+  }
+
+  pub fn set_role(&mut self, role: Role, authority: Pubkey) {
+    match role {
+      Role::Pauser => self.pauser = authority,
+      Role::MarketOperator => self.market_operator = authority,
+      Role::FeeManager => self.fee_manager = authority,
+    }
+  }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::{errors::ProtocolError, Position};
+
+/// Referral rewards earned by `referrer` in one stake mint. `collect_referral_reward` credits
+/// rewards here, so settlement never touches referrers. Tokens sit in `vault`, an ATA owned by
+/// this account; for native SOL the lamports sit on this account itself.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerAccount {
+  pub referrer: Pubkey,
+  pub mint: Pubkey,
+  pub vault: Pubkey,
+  /// Credited and not yet withdrawn.
+  pub accrued: u64,
+  /// Lifetime rewards credited.
+  pub total_earned: u64,
+  pub bump: u8,
+}
+
+impl ReferrerAccount {
+  /// Referrer carried by a new position of `user` in `mint`, if a referrer account was passed.
+  /// Rejects self-referrals and accounts for another mint.
+  pub fn referrer_for(
+    account: Option<&Self>,
+    user: &Pubkey,
+    mint: &Pubkey,
+  ) -> Result<Option<Pubkey>> {
+    let Some(account) = account else {
+      return Ok(None);
+    };
+
+    require!(
+      account.referrer != *user && account.mint == *mint,
+      ProtocolError::InvalidReferrer
+    );
+
+    Ok(Some(account.referrer))
+  }
+
+  /// Key of an optional referrer account as a PDA seed; empty when none was passed.
+  pub fn seed<'info>(account: &Option<Account<'info, Self>>) -> &'info [u8] {
+    account
+      .as_ref()
+      .map_or(&[], |account| account.to_account_info().key.as_ref())
+  }
+
+  /// Check that this is the account `position.referrer` earns into for `mint`.
+  pub fn check_for(&self, position: &Position, mint: &Pubkey) -> Result<()> {
+    require!(
+      position.referrer == Some(self.referrer) && self.mint == *mint,
+      ProtocolError::InvalidReferrer
+    );
+
+    Ok(())
+  }
+
+  pub fn credit(&mut self, amount: u64) -> Result<()> {
+    self.accrued = self.accrued.checked_add(amount).ok_or(ProtocolError::MathOverflow)?;
+    self.total_earned = self
+      .total_earned
+      .checked_add(amount)
+      .ok_or(ProtocolError::MathOverflow)?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+
+use crate::errors::ProtocolError;
+
+/// Token-2022 mint extensions that would let someone other than the vault authority move or
+/// lock vault funds, or hide balances from the program.
+pub const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 5] = [
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+    ExtensionType::NonTransferable,
+    ExtensionType::DefaultAccountState,
+    ExtensionType::ConfidentialTransferMint,
+];
+
+/// Reject stake mints the vault cannot hold safely. Legacy SPL Token mints have no
+/// extensions and always pass.
+pub fn ensure_supported_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    for extension in state.get_extension_types()? {
+        require!(
+            !UNSUPPORTED_MINT_EXTENSIONS.contains(&extension),
+            ProtocolError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
@@ -6,13 +6,14 @@ import {
   BPS_MAX,
   program,
   protocolPda,
+  updateProtocol,
 } from "./helpers";
 
 describe("initialize_protocol", () => {
   it("initializes protocol with valid fee (3%)", async () => {
     const protocol = protocolPda(program.programId);
     await program.methods
-      .initializeProtocol(300, admin.publicKey)
+      .initializeProtocol(300, admin.publicKey, new anchor.BN(0))
       .accounts(accounts({
         admin: admin.publicKey,
         protocol,
@@ -25,14 +26,15 @@ describe("initialize_protocol", () => {
     expect(data.treasury.equals(admin.publicKey)).to.be.true;
     expect(data.protocolFeeBps).to.equal(300);
     expect(data.marketCount.toNumber()).to.equal(0);
-    expect(data.paused).to.be.false;
+    expect(data.pauseFlags).to.equal(0);
+    expect(data.timelockDelaySecs.toNumber()).to.equal(0);
   });
 
   it("fails when protocol account already exists", async () => {
     const protocol = protocolPda(program.programId);
     try {
       await program.methods
-        .initializeProtocol(100, admin.publicKey)
+        .initializeProtocol(100, admin.publicKey, new anchor.BN(0))
         .accounts(accounts({
           admin: admin.publicKey,
           protocol,
@@ -51,27 +53,13 @@ describe("initialize_protocol", () => {
     }
   });
 
-  it("allows fee 0 and 10000 (edge) via update", async () => {
+  it("allows fee 0 and 10000 (edge) via protocol update", async () => {
     const protocol = protocolPda(program.programId);
-    await program.methods
-      .updateProtocol(0, admin.publicKey, false)
-      .accounts(accounts({
-        adminAuthority: admin.publicKey,
-        protocol,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      }))
-      .rpc();
+    await updateProtocol({ protocolFeeBps: 0 });
     let data = await program.account.protocol.fetch(protocol);
     expect(data.protocolFeeBps).to.equal(0);
 
-    await program.methods
-      .updateProtocol(BPS_MAX, admin.publicKey, false)
-      .accounts(accounts({
-        adminAuthority: admin.publicKey,
-        protocol,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      }))
-      .rpc();
+    await updateProtocol({ protocolFeeBps: BPS_MAX });
     data = await program.account.protocol.fetch(protocol);
     expect(data.protocolFeeBps).to.equal(BPS_MAX);
   });
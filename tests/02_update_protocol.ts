@@ -5,44 +5,55 @@ import {
   admin,
   BPS_MAX,
   delay,
+  PAUSE_ALL,
   program,
   protocolPda,
+  scoringAuthority,
+  updateProtocol,
   SUITE_DELAY_MS,
 } from "./helpers";
 
-describe("update_protocol", () => {
-  before(async () => await delay(SUITE_DELAY_MS));
-  it("updates fee, treasury, and paused", async () => {
-    const protocol = protocolPda(program.programId);
+describe("protocol updates", () => {
+  let protocol: anchor.web3.PublicKey;
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+  });
+
+  it("queues and executes fee, treasury and scoring authority", async () => {
     const newTreasury = anchor.web3.Keypair.generate().publicKey;
-    await program.methods
-      .updateProtocol(500, newTreasury, true)
-      .accounts(accounts({
-        adminAuthority: admin.publicKey,
-        protocol,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      }))
-      .rpc();
+    await updateProtocol({
+      protocolFeeBps: 500,
+      treasury: newTreasury,
+      scoringAuthority: scoringAuthority.publicKey,
+    });
 
     const data = await program.account.protocol.fetch(protocol);
     expect(data.protocolFeeBps).to.equal(500);
     expect(data.treasury.equals(newTreasury)).to.be.true;
-    expect(data.paused).to.be.true;
+    expect(data.scoringAuthority.equals(scoringAuthority.publicKey)).to.be.true;
+    expect(data.pendingUpdate).to.be.null;
+  });
+
+  it("pauses immediately", async () => {
+    await program.methods
+      .setPauseFlags(PAUSE_ALL)
+      .accounts(accounts({ pauser: admin.publicKey, protocol }))
+      .rpc();
+
+    const data = await program.account.protocol.fetch(protocol);
+    expect(data.pauseFlags).to.equal(PAUSE_ALL);
   });
 
   it("rejects non-admin signer", async () => {
-    const protocol = protocolPda(program.programId);
     const other = anchor.web3.Keypair.generate();
     // No airdrop: provider wallet pays tx fee; we only need other to sign so program rejects Unauthorized.
 
     try {
       await program.methods
-        .updateProtocol(100, admin.publicKey, false)
-        .accounts(accounts({
-          adminAuthority: other.publicKey,
-          protocol,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        }))
+        .queueProtocolUpdate(null, null, null, other.publicKey, null, null, null, null)
+        .accounts(accounts({ authority: other.publicKey, protocol }))
         .signers([other])
         .rpc();
       expect.fail("should have thrown");
@@ -52,16 +63,26 @@ describe("update_protocol", () => {
     }
   });
 
+  it("rejects an update that changes nothing", async () => {
+    const other = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .queueProtocolUpdate(null, null, null, null, null, null, null, null)
+        .accounts(accounts({ authority: other.publicKey, protocol }))
+        .signers([other])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("EmptyProtocolUpdate");
+    }
+  });
+
   it("rejects fee > 10000", async () => {
-    const protocol = protocolPda(program.programId);
     try {
       await program.methods
-        .updateProtocol(BPS_MAX + 1, admin.publicKey, false)
-        .accounts(accounts({
-          adminAuthority: admin.publicKey,
-          protocol,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        }))
+        .queueProtocolUpdate(BPS_MAX + 1, null, null, null, null, null, null, null)
+        .accounts(accounts({ authority: admin.publicKey, protocol }))
         .rpc();
       expect.fail("should have thrown");
     } catch (e: unknown) {
@@ -69,4 +90,127 @@ describe("update_protocol", () => {
       expect(err.message || err).to.include("InvalidProtocolFeeBps");
     }
   });
+
+  it("rejects an empty claim window", async () => {
+    try {
+      await program.methods
+        .queueProtocolUpdate(null, null, null, null, null, null, null, new anchor.BN(0))
+        .accounts(accounts({ authority: admin.publicKey, protocol }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidClaimWindow");
+    }
+  });
+
+  it("holds queued updates until the delay has passed", async () => {
+    const delaySecs = 3;
+    await updateProtocol({ timelockDelaySecs: delaySecs });
+
+    await program.methods
+      .queueProtocolUpdate(100, null, null, null, null, null, null, null)
+      .accounts(accounts({ authority: admin.publicKey, protocol }))
+      .rpc();
+
+    let data = await program.account.protocol.fetch(protocol);
+    expect(data.pendingUpdate!.protocolFeeBps).to.equal(100);
+    expect(data.protocolFeeBps).to.equal(500);
+
+    try {
+      await program.methods.executeProtocolUpdate().accounts(accounts({ protocol })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("TimelockNotElapsed");
+    }
+
+    await program.methods
+      .cancelProtocolUpdate({ fees: {} })
+      .accounts(accounts({ authority: admin.publicKey, protocol }))
+      .rpc();
+    data = await program.account.protocol.fetch(protocol);
+    expect(data.pendingUpdate).to.be.null;
+    expect(data.protocolFeeBps).to.equal(500);
+
+    // Restore the zero delay for later suites; the change itself waits out the current delay
+    await program.methods
+      .queueProtocolUpdate(null, null, null, null, null, new anchor.BN(0), null, null)
+      .accounts(accounts({ authority: admin.publicKey, protocol }))
+      .rpc();
+    const adminEta = (await program.account.protocol.fetch(protocol)).pendingUpdate!.adminEta!;
+
+    // A later fee change gets its own timer and leaves the queued admin change's alone
+    await delay(2000);
+    await program.methods
+      .queueProtocolUpdate(500, null, null, null, null, null, null, null)
+      .accounts(accounts({ authority: admin.publicKey, protocol }))
+      .rpc();
+    data = await program.account.protocol.fetch(protocol);
+    expect(data.pendingUpdate!.adminEta!.toNumber()).to.equal(adminEta.toNumber());
+    expect(data.pendingUpdate!.feeEta!.toNumber()).to.be.greaterThan(adminEta.toNumber());
+    expect(data.pendingUpdate!.timelockDelaySecs.toNumber()).to.equal(0);
+
+    await delay((delaySecs + 2) * 1000);
+    await program.methods.executeProtocolUpdate().accounts(accounts({ protocol })).rpc();
+
+    data = await program.account.protocol.fetch(protocol);
+    expect(data.timelockDelaySecs.toNumber()).to.equal(0);
+  });
+
+  it("cancels each group separately, only by the role that queues it", async () => {
+    const feeManager = anchor.web3.Keypair.generate();
+    await program.methods
+      .grantRole({ feeManager: {} }, feeManager.publicKey)
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+
+    const cancel = (group: { fees: {} } | { admin: {} }, authority: anchor.web3.Keypair | null) =>
+      program.methods
+        .cancelProtocolUpdate(group)
+        .accounts(accounts({ authority: authority?.publicKey ?? admin.publicKey, protocol }))
+        .signers(authority ? [authority] : [])
+        .rpc();
+    const expectError = async (call: Promise<unknown>, error: string) => {
+      try {
+        await call;
+        expect.fail("should have thrown");
+      } catch (e: unknown) {
+        const err = e as { message?: string };
+        expect(err.message || err).to.include(error);
+      }
+    };
+
+    await program.methods
+      .queueProtocolUpdate(100, null, null, null, null, null, null, null)
+      .accounts(accounts({ authority: feeManager.publicKey, protocol }))
+      .signers([feeManager])
+      .rpc();
+    await program.methods
+      .queueProtocolUpdate(null, null, null, null, null, null, new anchor.BN(7200), null)
+      .accounts(accounts({ authority: admin.publicKey, protocol }))
+      .rpc();
+    const { adminEta } = (await program.account.protocol.fetch(protocol)).pendingUpdate!;
+
+    await expectError(cancel({ fees: {} }, null), "Unauthorized");
+    await expectError(cancel({ admin: {} }, feeManager), "Unauthorized");
+
+    // Dropping the fee change leaves the admin change and its timer in place
+    await cancel({ fees: {} }, feeManager);
+    let data = await program.account.protocol.fetch(protocol);
+    expect(data.pendingUpdate!.feeEta).to.be.null;
+    expect(data.pendingUpdate!.protocolFeeBps).to.equal(data.protocolFeeBps);
+    expect(data.pendingUpdate!.adminEta!.toNumber()).to.equal(adminEta!.toNumber());
+    expect(data.pendingUpdate!.emergencyTimeoutSecs.toNumber()).to.equal(7200);
+    await expectError(cancel({ fees: {} }, feeManager), "NoPendingUpdate");
+
+    await cancel({ admin: {} }, null);
+    data = await program.account.protocol.fetch(protocol);
+    expect(data.pendingUpdate).to.be.null;
+
+    await program.methods
+      .grantRole({ feeManager: {} }, admin.publicKey)
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+  });
 });
@@ -11,11 +11,20 @@ import {
   admin,
   connection,
   delay,
+  ensureMintConfig,
+  mintConfigPda,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
   marketPda,
+  PAUSE_ALL,
   program,
   protocolPda,
+  updateProtocol,
   vaultAuthorityPda,
+  scoringAuthority,
   SUITE_DELAY_MS,
+  TWO_ITEMS,
   ZERO_ITEMS_HASH,
 } from "./helpers";
 
@@ -26,13 +35,14 @@ describe("create_market", () => {
   before(async () => {
     await delay(SUITE_DELAY_MS);
     protocol = protocolPda(program.programId);
+    await updateProtocol({
+      protocolFeeBps: 300,
+      treasury: admin.publicKey,
+      scoringAuthority: scoringAuthority.publicKey,
+    });
     await program.methods
-      .updateProtocol(300, admin.publicKey, false)
-      .accounts(accounts({
-        adminAuthority: admin.publicKey,
-        protocol,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      }))
+      .setPauseFlags(0)
+      .accounts(accounts({ pauser: admin.publicKey, protocol }))
       .rpc();
 
     tokenMint = await createMint(
@@ -45,6 +55,7 @@ describe("create_market", () => {
       undefined,
       TOKEN_PROGRAM_ID
     );
+    await ensureMintConfig(tokenMint);
   });
 
   it("creates market with valid params", async () => {
@@ -68,18 +79,24 @@ describe("create_market", () => {
       .createMarket(
         new anchor.BN(startTs),
         new anchor.BN(endTs),
-        ZERO_ITEMS_HASH,
-        2
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
       )
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        creator: admin.publicKey,
         protocol,
         market,
+        marketItems: marketItemsPda(program.programId, market),
         vaultAuthority,
         vault: vaultAddress,
         tokenMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        creatorTokenAccount: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -91,6 +108,11 @@ describe("create_market", () => {
     expect(m.endTs.toNumber()).to.equal(endTs);
     expect(m.status.draft !== undefined).to.be.true;
     expect(m.totalRawStake.toNumber()).to.equal(0);
+    expect(m.feeBps).to.equal(300);
+
+    const items = await program.account.marketItems.fetch(marketItemsPda(program.programId, market));
+    expect(items.labels).to.deep.equal(TWO_ITEMS);
+    expect(m.itemsHash).to.deep.equal(itemsHash(items.labels));
 
     // Create second market with future start_ts so open_market and close_market can use it
     const nextCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
@@ -105,16 +127,19 @@ describe("create_market", () => {
       ASSOCIATED_TOKEN_PROGRAM_ID
     );
     await program.methods
-      .createMarket(new anchor.BN(t), new anchor.BN(t + 100), ZERO_ITEMS_HASH, 2)
+      .createMarket(new anchor.BN(t), new anchor.BN(t + 100), itemsHash(TWO_ITEMS), 2, TWO_ITEMS, FLAT_SCHEDULE, 0)
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        creator: admin.publicKey,
         protocol,
         market: market1,
+        marketItems: marketItemsPda(program.programId, market1),
         vaultAuthority: vaultAuthority1,
         vault: vault1,
         tokenMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        creatorTokenAccount: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -137,18 +162,24 @@ describe("create_market", () => {
         .createMarket(
           new anchor.BN(10),
           new anchor.BN(10),
-          ZERO_ITEMS_HASH,
-          2
+          itemsHash(TWO_ITEMS),
+          2,
+          TWO_ITEMS,
+          FLAT_SCHEDULE,
+          0
         )
         .accounts(accounts({
-          adminAuthority: admin.publicKey,
+          creator: admin.publicKey,
           protocol,
           market,
+          marketItems: marketItemsPda(program.programId, market),
           vaultAuthority,
           vault: vaultAddress,
           tokenMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
+          creatorTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         }))
         .rpc();
@@ -173,16 +204,19 @@ describe("create_market", () => {
 
     try {
       await program.methods
-        .createMarket(new anchor.BN(0), new anchor.BN(100), ZERO_ITEMS_HASH, 1)
+        .createMarket(new anchor.BN(0), new anchor.BN(100), itemsHash(["Yes"]), 1, ["Yes"], FLAT_SCHEDULE, 0)
         .accounts(accounts({
-          adminAuthority: admin.publicKey,
+          creator: admin.publicKey,
           protocol,
           market,
+          marketItems: marketItemsPda(program.programId, market),
           vaultAuthority,
           vault: vaultAddress,
           tokenMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
+          creatorTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         }))
         .rpc();
@@ -193,14 +227,85 @@ describe("create_market", () => {
     }
   });
 
+  it("rejects labels that don't hash to items_hash", async () => {
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    const vaultAuthority = vaultAuthorityPda(program.programId, market);
+    const vaultAddress = getAssociatedTokenAddressSync(
+      tokenMint,
+      vaultAuthority,
+      true,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    try {
+      await program.methods
+        .createMarket(new anchor.BN(0), new anchor.BN(100), ZERO_ITEMS_HASH, 2, TWO_ITEMS, FLAT_SCHEDULE, 0)
+        .accounts(accounts({
+          creator: admin.publicKey,
+          protocol,
+          market,
+          marketItems: marketItemsPda(program.programId, market),
+          vaultAuthority,
+          vault: vaultAddress,
+          tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
+          creatorTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("ItemsHashMismatch");
+    }
+  });
+
+  it("rejects a multiplier schedule that rises over time", async () => {
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    const vaultAuthority = vaultAuthorityPda(program.programId, market);
+    const vaultAddress = getAssociatedTokenAddressSync(
+      tokenMint,
+      vaultAuthority,
+      true,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const risingSchedule = { linear: { startMultiplierBps: 10_000, endMultiplierBps: 20_000 } };
+
+    try {
+      await program.methods
+        .createMarket(new anchor.BN(0), new anchor.BN(100), itemsHash(TWO_ITEMS), 2, TWO_ITEMS, risingSchedule, 0)
+        .accounts(accounts({
+          creator: admin.publicKey,
+          protocol,
+          market,
+          marketItems: marketItemsPda(program.programId, market),
+          vaultAuthority,
+          vault: vaultAddress,
+          tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
+          creatorTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidMultiplierSchedule");
+    }
+  });
+
   it("rejects when protocol is paused", async () => {
     await program.methods
-      .updateProtocol(300, admin.publicKey, true)
-      .accounts(accounts({
-        adminAuthority: admin.publicKey,
-        protocol,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      }))
+      .setPauseFlags(PAUSE_ALL)
+      .accounts(accounts({ pauser: admin.publicKey, protocol }))
       .rpc();
 
     const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
@@ -216,16 +321,19 @@ describe("create_market", () => {
 
     try {
       await program.methods
-        .createMarket(new anchor.BN(0), new anchor.BN(100), ZERO_ITEMS_HASH, 2)
+        .createMarket(new anchor.BN(0), new anchor.BN(100), itemsHash(TWO_ITEMS), 2, TWO_ITEMS, FLAT_SCHEDULE, 0)
         .accounts(accounts({
-          adminAuthority: admin.publicKey,
+          creator: admin.publicKey,
           protocol,
           market,
+          marketItems: marketItemsPda(program.programId, market),
           vaultAuthority,
           vault: vaultAddress,
           tokenMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
+          creatorTokenAccount: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         }))
         .rpc();
@@ -236,12 +344,8 @@ describe("create_market", () => {
     }
 
     await program.methods
-      .updateProtocol(300, admin.publicKey, false)
-      .accounts(accounts({
-        adminAuthority: admin.publicKey,
-        protocol,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      }))
+      .setPauseFlags(0)
+      .accounts(accounts({ pauser: admin.publicKey, protocol }))
       .rpc();
   });
 });
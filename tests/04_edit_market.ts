@@ -5,11 +5,14 @@ import {
   admin,
   connection,
   delay,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
   marketPda,
   program,
   protocolPda,
   SUITE_DELAY_MS,
-  ZERO_ITEMS_HASH,
+  TWO_ITEMS,
 } from "./helpers";
 
 describe("edit_market", () => {
@@ -25,15 +28,16 @@ describe("edit_market", () => {
     const startTs = blockTime;
     const endTs = blockTime + 86400;
 
-    const newHash = [...ZERO_ITEMS_HASH];
-    newHash[0] = 1;
+    const labels = ["Red", "Green", "Blue"];
+    const newHash = itemsHash(labels);
 
     await program.methods
-      .editMarket(new anchor.BN(startTs), new anchor.BN(endTs), newHash, 3)
+      .editMarket(new anchor.BN(startTs), new anchor.BN(endTs), newHash, 3, labels, FLAT_SCHEDULE)
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        marketOperator: admin.publicKey,
         protocol,
         market,
+        marketItems: marketItemsPda(program.programId, market),
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -42,7 +46,10 @@ describe("edit_market", () => {
     expect(m.startTs.toNumber()).to.equal(startTs);
     expect(m.endTs.toNumber()).to.equal(endTs);
     expect(m.itemCount).to.equal(3);
-    expect(m.itemsHash[0]).to.equal(1);
+    expect(m.itemsHash).to.deep.equal(newHash);
+
+    const items = await program.account.marketItems.fetch(marketItemsPda(program.programId, market));
+    expect(items.labels).to.deep.equal(labels);
   });
 
   it("rejects edit when market is not draft", async () => {
@@ -51,18 +58,19 @@ describe("edit_market", () => {
     const market = marketPda(program.programId, marketCount - 2);
 
     await program.methods.openMarket().accounts(accounts({
-      adminAuthority: admin.publicKey,
+      marketOperator: admin.publicKey,
       protocol,
       market,
     })).rpc();
 
     try {
       await program.methods
-        .editMarket(new anchor.BN(0), new anchor.BN(86400), ZERO_ITEMS_HASH, 2)
+        .editMarket(new anchor.BN(0), new anchor.BN(86400), itemsHash(TWO_ITEMS), 2, TWO_ITEMS, FLAT_SCHEDULE)
         .accounts(accounts({
-          adminAuthority: admin.publicKey,
+          marketOperator: admin.publicKey,
           protocol,
           market,
+          marketItems: marketItemsPda(program.programId, market),
           systemProgram: anchor.web3.SystemProgram.programId,
         }))
         .rpc();
@@ -21,7 +21,7 @@ describe("open_market", () => {
 
     try {
       await program.methods.openMarket().accounts(accounts({
-        adminAuthority: admin.publicKey,
+        marketOperator: admin.publicKey,
         protocol,
         market,
       })).rpc();
@@ -10,7 +10,9 @@ import {
   accounts,
   admin,
   connection,
+  cpiEvents,
   delay,
+  mintConfigPda,
   marketPda,
   MAX_MULTIPLIER,
   positionPda,
@@ -89,15 +91,15 @@ describe("place_position", () => {
   });
 
   it("places position and updates market totals", async () => {
-    const position = positionPda(program.programId, market, user.publicKey);
+    const position = positionPda(program.programId, market, user.publicKey, 0);
     const rawStake = 100_000;
-    const effectiveStake = 100_000;
+    const minEffectiveStake = 100_000;
 
-    await program.methods
+    const signature = await program.methods
       .placePosition(
         0,
         new anchor.BN(rawStake),
-        new anchor.BN(effectiveStake)
+        new anchor.BN(minEffectiveStake)
       )
       .accounts(accounts({
         user: user.publicKey,
@@ -108,7 +110,12 @@ describe("place_position", () => {
         userTokenAccount: userAta,
         vault,
         tokenProgram: TOKEN_PROGRAM_ID,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
         systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
       }))
       .signers([user])
       .rpc();
@@ -118,14 +125,25 @@ describe("place_position", () => {
     expect(pos.user.equals(user.publicKey)).to.be.true;
     expect(pos.selectedItemIndex).to.equal(0);
     expect(pos.rawStake.toNumber()).to.equal(rawStake);
+    // Market uses a flat 1x schedule
+    expect(pos.effectiveStake.toNumber()).to.equal(rawStake);
     expect(pos.claimed).to.be.false;
 
     const marketData = await program.account.market.fetch(market);
     expect(marketData.totalRawStake.toNumber()).to.equal(rawStake);
+
+    const [placed] = (await cpiEvents(signature)).filter((e) => e.name === "positionPlaced");
+    expect(placed, "PositionPlaced event").to.not.be.undefined;
+    expect(placed.data.position.equals(position)).to.be.true;
+    expect(placed.data.selectedItemIndex).to.equal(0);
+    expect(placed.data.rawStake.toNumber()).to.equal(rawStake);
+    expect(placed.data.effectiveStakePerItem[0].toString()).to.equal(
+      marketData.effectiveStakePerItem[0].toString()
+    );
   });
 
   it("rejects raw_stake 0", async () => {
-    const position = positionPda(program.programId, market, rejectUser.publicKey);
+    const position = positionPda(program.programId, market, rejectUser.publicKey, 0);
     try {
       await program.methods
         .placePosition(0, new anchor.BN(0), new anchor.BN(100))
@@ -138,7 +156,12 @@ describe("place_position", () => {
           userTokenAccount: rejectUserAta,
           vault,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
           systemProgram: anchor.web3.SystemProgram.programId,
+          referrerAccount: null,
+          marketReferral: null,
         }))
         .signers([rejectUser])
         .rpc();
@@ -161,8 +184,8 @@ describe("place_position", () => {
   });
 
   it("rejects invalid item index", async () => {
-    const position = positionPda(program.programId, market, rejectUser.publicKey);
     const m = await program.account.market.fetch(market);
+    const position = positionPda(program.programId, market, rejectUser.publicKey, m.itemCount);
     try {
       await program.methods
         .placePosition(
@@ -179,7 +202,12 @@ describe("place_position", () => {
           userTokenAccount: rejectUserAta,
           vault,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
           systemProgram: anchor.web3.SystemProgram.programId,
+          referrerAccount: null,
+          marketReferral: null,
         }))
         .signers([rejectUser])
         .rpc();
@@ -201,13 +229,13 @@ describe("place_position", () => {
     }
   });
 
-  it("rejects effective_stake > raw_stake * MAX_MULTIPLIER", async () => {
-    const position = positionPda(program.programId, market, rejectUser.publicKey);
+  it("rejects when derived effective stake is below min_effective_stake", async () => {
+    const position = positionPda(program.programId, market, rejectUser.publicKey, 1);
     const raw = 1000;
-    const effective = raw * MAX_MULTIPLIER + 1;
+    const minEffective = raw * MAX_MULTIPLIER + 1;
     try {
       await program.methods
-        .placePosition(1, new anchor.BN(raw), new anchor.BN(effective))
+        .placePosition(1, new anchor.BN(raw), new anchor.BN(minEffective))
         .accounts(accounts({
           user: rejectUser.publicKey,
           protocol,
@@ -217,7 +245,12 @@ describe("place_position", () => {
           userTokenAccount: rejectUserAta,
           vault,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
           systemProgram: anchor.web3.SystemProgram.programId,
+          referrerAccount: null,
+          marketReferral: null,
         }))
         .signers([rejectUser])
         .rpc();
@@ -235,7 +268,7 @@ describe("place_position", () => {
       const logs = (err?.logs ?? err?.transactionLogs ?? [])?.join(" ") ?? "";
       const txMsg = err?.transactionMessage ?? "";
       const msg = (err?.message ?? String(e)) + code + num + logs + txMsg;
-      expect(msg).to.match(/EffectiveStakeTooLarge|6007|1777|multiplier/i);
+      expect(msg).to.match(/EffectiveStakeBelowMinimum|accepted minimum/i);
     }
   });
 });
@@ -1,5 +1,6 @@
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
@@ -46,8 +47,13 @@ describe("settle_market", () => {
         market,
         vaultAuthority,
         vault: m.vault,
+        tokenMint: m.tokenMint,
+        treasury: admin.publicKey,
         treasuryTokenAccount: treasuryAta.address,
+        creator: admin.publicKey,
+        creatorTokenAccount: treasuryAta.address,
         tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       })).rpc();
       expect.fail("should have thrown");
@@ -57,4 +63,35 @@ describe("settle_market", () => {
       expect(msg).to.match(/InvalidMarketState|6003|required state/i);
     }
   });
+
+  it("rejects a fee destination other than the protocol treasury", async () => {
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 2);
+    const vaultAuthority = vaultAuthorityPda(program.programId, market);
+    const m = await program.account.market.fetch(market);
+    const attacker = anchor.web3.Keypair.generate().publicKey;
+
+    try {
+      await program.methods.settleMarket().accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vaultAuthority,
+        vault: m.vault,
+        tokenMint: m.tokenMint,
+        treasury: attacker,
+        treasuryTokenAccount: getAssociatedTokenAddressSync(m.tokenMint, attacker),
+        creator: m.creator,
+        creatorTokenAccount: getAssociatedTokenAddressSync(m.tokenMint, m.creator),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidTreasury");
+    }
+  });
 });
@@ -25,7 +25,7 @@ describe("claim_payout", () => {
     const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
     const market = marketPda(program.programId, marketCount - 1);
     const wallet = program.provider.wallet;
-    const position = positionPda(program.programId, market, wallet.publicKey);
+    const position = positionPda(program.programId, market, wallet.publicKey, 0);
     const vaultAuthority = vaultAuthorityPda(program.programId, market);
     const m = await program.account.market.fetch(market);
     const userAta = await getOrCreateAssociatedTokenAccount(
@@ -41,13 +41,14 @@ describe("claim_payout", () => {
     );
 
     try {
-      await program.methods.claimPayout().accounts(accounts({
+      await program.methods.claimPayout(false).accounts(accounts({
         user: wallet.publicKey,
         market,
         position,
         vaultAuthority,
         vault: m.vault,
         userTokenAccount: userAta.address,
+        tokenMint: m.tokenMint,
         tokenProgram: TOKEN_PROGRAM_ID,
       })).rpc();
       expect.fail("should have thrown");
@@ -13,6 +13,11 @@ import {
   admin,
   connection,
   delay,
+  ensureMintConfig,
+  mintConfigPda,
+  EARLY_BIRD_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
   marketPda,
   positionPda,
   program,
@@ -22,15 +27,14 @@ import {
 } from "./helpers";
 
 const ITEM_COUNT = 4;
+const WINNING_ITEM = 1;
 const PROTOCOL_FEE_BPS = 300; // 3%
 const LAMPORTS_PER_USER = 500_000_000;
 const TOKEN_DECIMALS = 6;
 /** Devnet: create + open + fund users can take 30+ s; place must run before end_ts. Wait this long before close. */
 const END_WINDOW_SEC = 120;
 
-function itemsHashFromLabel(_label: string): number[] {
-  return new Array(32).fill(0).map((_, i) => (i % 3)) as number[];
-}
+const ITEM_LABELS = ["Alpha", "Bravo", "Charlie", "Delta"];
 
 describe("realtime opinion market (E2E)", () => {
   let protocol: anchor.web3.PublicKey;
@@ -55,14 +59,10 @@ describe("realtime opinion market (E2E)", () => {
 
     // Ensure protocol is live and not paused
     const proto = await program.account.protocol.fetch(protocol);
-    if (proto.paused) {
+    if (proto.pauseFlags !== 0) {
       await program.methods
-        .updateProtocol(proto.protocolFeeBps, proto.treasury, false)
-        .accounts(accounts({
-          adminAuthority: admin.publicKey,
-          protocol,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        }))
+        .setPauseFlags(0)
+        .accounts(accounts({ pauser: admin.publicKey, protocol }))
         .rpc();
     }
 
@@ -76,6 +76,7 @@ describe("realtime opinion market (E2E)", () => {
       undefined,
       TOKEN_PROGRAM_ID
     );
+    await ensureMintConfig(tokenMint);
 
     // Treasury ATA (protocol treasury = admin for tests)
     const treasuryAtaAccount = await getOrCreateAssociatedTokenAccount(
@@ -113,18 +114,24 @@ describe("realtime opinion market (E2E)", () => {
       .createMarket(
         new anchor.BN(startTs),
         new anchor.BN(endTs),
-        itemsHashFromLabel("MVP 2025"),
-        ITEM_COUNT
+        itemsHash(ITEM_LABELS),
+        ITEM_COUNT,
+        ITEM_LABELS,
+        EARLY_BIRD_SCHEDULE,
+        0
       )
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        creator: admin.publicKey,
         protocol,
         market,
+        marketItems: marketItemsPda(program.programId, market),
         vaultAuthority,
         vault: vaultAddress,
         tokenMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        creatorTokenAccount: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -134,7 +141,7 @@ describe("realtime opinion market (E2E)", () => {
     await program.methods
       .openMarket()
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        marketOperator: admin.publicKey,
         protocol,
         market,
       }))
@@ -142,11 +149,11 @@ describe("realtime opinion market (E2E)", () => {
 
     // Create 4 participants with SOL + tokens
     const names = ["Alice", "Bob", "Carol", "Dave"];
-    const stakes: { raw: number; effective: number }[] = [
-      { raw: 100_000, effective: 100_000 },
-      { raw: 250_000, effective: 400_000 },
-      { raw: 150_000, effective: 200_000 },
-      { raw: 80_000, effective: 80_000 },
+    const stakes: { raw: number }[] = [
+      { raw: 100_000 },
+      { raw: 250_000 },
+      { raw: 150_000 },
+      { raw: 80_000 },
     ];
     const totalMint = stakes.reduce((s, x) => s + x.raw, 0) * 2; // extra buffer
     await mintTo(
@@ -158,6 +165,8 @@ describe("realtime opinion market (E2E)", () => {
       totalMint
     );
 
+    // Bob and Carol both back the winning item so the pool is split between them
+    const itemIndices = [0, WINNING_ITEM, WINNING_ITEM, 2];
     for (let i = 0; i < 4; i++) {
       const keypair = anchor.web3.Keypair.generate();
       await program.provider.sendAndConfirm(
@@ -192,40 +201,46 @@ describe("realtime opinion market (E2E)", () => {
         name: names[i],
         keypair,
         ata: ata.address,
-        itemIndex: i,
+        itemIndex: itemIndices[i],
         rawStake: stakes[i].raw,
-        effectiveStake: stakes[i].effective,
+        effectiveStake: 0, // derived on-chain from the multiplier schedule
       });
     }
   });
 
-  it("runs full lifecycle: place positions → close → settle → claim proportional payouts", async () => {
+  it("runs full lifecycle: place positions → close → resolve → settle → claim winner payouts", async () => {
     console.log("[E2E] Starting full lifecycle test (market is Open)");
     const marketBefore = await program.account.market.fetch(market);
     expect(marketBefore.status.open !== undefined).to.be.true;
 
-    // Scenario: Alice (item 0), Bob (item 1), Carol (item 2), Dave (item 3) each stake
-    // with different amounts; payouts are proportional to effective_stake / total_effective_stake.
+    // Scenario: Alice (item 0), Bob (item 1), Carol (item 1), Dave (item 2) each stake
+    // with different amounts; item 1 wins and Bob/Carol split the pool by
+    // effective_stake / effective_stake_per_item[winner].
 
     // ─── Phase 1: Everyone places positions ────────────────────────────────────
     for (const p of participants) {
-      console.log(`[E2E] Instruction: place_position (${p.name}, item ${p.itemIndex}, raw=${p.rawStake}, effective=${p.effectiveStake})`);
+      console.log(`[E2E] Instruction: place_position (${p.name}, item ${p.itemIndex}, raw=${p.rawStake})`);
       await program.methods
         .placePosition(
           p.itemIndex,
           new anchor.BN(p.rawStake),
-          new anchor.BN(p.effectiveStake)
+          new anchor.BN(p.rawStake)
         )
         .accounts(accounts({
           user: p.keypair.publicKey,
           protocol,
           market,
-          position: positionPda(program.programId, market, p.keypair.publicKey),
+          position: positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex),
           tokenMint,
           userTokenAccount: p.ata,
           vault: vaultAddress,
           tokenProgram: TOKEN_PROGRAM_ID,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
           systemProgram: anchor.web3.SystemProgram.programId,
+          referrerAccount: null,
+          marketReferral: null,
         }))
         .signers([p.keypair])
         .rpc();
@@ -235,7 +250,18 @@ describe("realtime opinion market (E2E)", () => {
     const totalRawStake = m.totalRawStake.toNumber();
     const totalEffectiveStake = m.totalEffectiveStake.toNumber();
     expect(totalRawStake).to.equal(580_000);
-    expect(totalEffectiveStake).to.equal(780_000);
+
+    // Early-bird schedule: each effective stake is between 1x and 2x raw stake
+    let summedEffectiveStake = 0;
+    for (const p of participants) {
+      const pos = await program.account.position.fetch(
+        positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex)
+      );
+      p.effectiveStake = pos.effectiveStake.toNumber();
+      expect(p.effectiveStake).to.be.within(p.rawStake, p.rawStake * 2);
+      summedEffectiveStake += p.effectiveStake;
+    }
+    expect(totalEffectiveStake).to.equal(summedEffectiveStake);
     console.log(`[E2E] Market totals: totalRawStake=${totalRawStake}, totalEffectiveStake=${totalEffectiveStake}`);
 
     // ─── Phase 2: Close market (after end_ts) ───────────────────────────────────
@@ -257,7 +283,28 @@ describe("realtime opinion market (E2E)", () => {
     m = await program.account.market.fetch(market);
     expect(m.status.closed !== undefined).to.be.true;
 
-    // ─── Phase 3: Settle market (protocol fee → treasury, set distributable_pool) ─
+    // ─── Phase 3: Resolve market (record winning item) ──────────────────────────
+    console.log(`[E2E] Instruction: resolve_market (winner = item ${WINNING_ITEM})`);
+    await program.methods
+      .resolveMarket(WINNING_ITEM)
+      .accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+      }))
+      .rpc();
+
+    m = await program.account.market.fetch(market);
+    expect(m.status.resolved !== undefined).to.be.true;
+    expect(m.winningItemIndex).to.equal(WINNING_ITEM);
+    const winningEffectiveStake = m.effectiveStakePerItem[WINNING_ITEM].toNumber();
+    expect(winningEffectiveStake).to.equal(
+      participants
+        .filter((p) => p.itemIndex === WINNING_ITEM)
+        .reduce((sum, p) => sum + p.effectiveStake, 0)
+    );
+
+    // ─── Phase 4: Settle market (protocol fee → treasury, set distributable_pool) ─
     console.log("[E2E] Instruction: settle_market");
     await program.methods
       .settleMarket()
@@ -267,8 +314,13 @@ describe("realtime opinion market (E2E)", () => {
         market,
         vaultAuthority,
         vault: vaultAddress,
+        tokenMint,
+        treasury: admin.publicKey,
         treasuryTokenAccount: treasuryAta,
+        creator: admin.publicKey,
+        creatorTokenAccount: treasuryAta,
         tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -283,8 +335,8 @@ describe("realtime opinion market (E2E)", () => {
     expect(distributablePool).to.equal(totalRawStake - expectedFee);
     console.log(`[E2E] Settled: protocolFeeAmount=${protocolFeeAmount}, distributablePool=${distributablePool}`);
 
-    // ─── Phase 4: Each participant claims their proportional share ───────────────
-    // Payout = (position.effective_stake / market.total_effective_stake) * distributable_pool
+    // ─── Phase 5: Each participant claims; only winners receive a share ─────────
+    // Payout = (position.effective_stake / effective_stake_per_item[winner]) * distributable_pool
     const balancesBefore: number[] = [];
     for (let i = 0; i < participants.length; i++) {
       const acc = await connection.getTokenAccountBalance(participants[i].ata);
@@ -294,14 +346,15 @@ describe("realtime opinion market (E2E)", () => {
     for (const p of participants) {
       console.log(`[E2E] Instruction: claim_payout (${p.name})`);
       await program.methods
-        .claimPayout()
+        .claimPayout(false)
         .accounts(accounts({
           user: p.keypair.publicKey,
           market,
-          position: positionPda(program.programId, market, p.keypair.publicKey),
+          position: positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex),
           vaultAuthority,
           vault: vaultAddress,
           userTokenAccount: p.ata,
+          tokenMint,
           tokenProgram: TOKEN_PROGRAM_ID,
         }))
         .signers([p.keypair])
@@ -311,13 +364,13 @@ describe("realtime opinion market (E2E)", () => {
     for (let i = 0; i < participants.length; i++) {
       const p = participants[i];
       const pos = await program.account.position.fetch(
-        positionPda(program.programId, market, p.keypair.publicKey)
+        positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex)
       );
       expect(pos.claimed).to.be.true;
 
-      const expectedPayout = Math.floor(
-        (Number(p.effectiveStake) * distributablePool) / totalEffectiveStake
-      );
+      const expectedPayout = p.itemIndex === WINNING_ITEM
+        ? Math.floor((Number(p.effectiveStake) * distributablePool) / winningEffectiveStake)
+        : 0;
       const accAfter = await connection.getTokenAccountBalance(p.ata);
       const balanceAfter = Number(accAfter.value.amount);
       const received = balanceAfter - balancesBefore[i];
@@ -327,10 +380,14 @@ describe("realtime opinion market (E2E)", () => {
       ).to.be.true;
     }
 
-    // Vault should be drained (allow tiny remainder from integer division rounding)
+    // Claims pay out exactly the pool: the last winner receives the rounding dust
+    m = await program.account.market.fetch(market);
+    expect(m.positionsClaimed.toNumber()).to.equal(participants.length);
+    expect(m.totalClaimed.toNumber()).to.equal(distributablePool);
+
     const vaultAfter = await connection.getTokenAccountBalance(vaultAddress);
     const vaultRemainder = Number(vaultAfter.value.amount);
     console.log(`[E2E] Vault balance after all claims: ${vaultRemainder}`);
-    expect(vaultRemainder <= 5, `vault remainder ${vaultRemainder} should be <= 5 (rounding dust)`).to.be.true;
+    expect(vaultRemainder).to.equal(0);
   });
 });
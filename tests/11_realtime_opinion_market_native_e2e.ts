@@ -5,6 +5,12 @@ import {
   admin,
   connection,
   delay,
+  ensureMintConfig,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  EARLY_BIRD_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
   marketPda,
   positionPda,
   program,
@@ -14,14 +20,13 @@ import {
 } from "./helpers";
 
 const ITEM_COUNT = 3;
+const WINNING_ITEM = 1;
 const PROTOCOL_FEE_BPS = 300; // 3%
 const LAMPORTS_PER_USER = 500_000_000;
 /** Devnet: create + open + fund users can take 30+ s; place must run before end_ts. */
 const END_WINDOW_SEC = 120;
 
-function itemsHashFromLabel(_label: string): number[] {
-  return new Array(32).fill(0).map((_, i) => (i % 3)) as number[];
-}
+const ITEM_LABELS = ["Alpha", "Bravo", "Charlie"];
 
 describe("realtime opinion market native SOL (E2E)", () => {
   let protocol: anchor.web3.PublicKey;
@@ -40,16 +45,13 @@ describe("realtime opinion market native SOL (E2E)", () => {
   before(async () => {
     await delay(SUITE_DELAY_MS);
     protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
 
     const proto = await program.account.protocol.fetch(protocol);
-    if (proto.paused) {
+    if (proto.pauseFlags !== 0) {
       await program.methods
-        .updateProtocol(proto.protocolFeeBps, proto.treasury, false)
-        .accounts(accounts({
-          adminAuthority: admin.publicKey,
-          protocol,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        }))
+        .setPauseFlags(0)
+        .accounts(accounts({ pauser: admin.publicKey, protocol }))
         .rpc();
     }
 
@@ -68,14 +70,19 @@ describe("realtime opinion market native SOL (E2E)", () => {
       .createMarketNative(
         new anchor.BN(startTs),
         new anchor.BN(endTs),
-        itemsHashFromLabel("Native MVP"),
-        ITEM_COUNT
+        itemsHash(ITEM_LABELS),
+        ITEM_COUNT,
+        ITEM_LABELS,
+        EARLY_BIRD_SCHEDULE,
+        0
       )
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        creator: admin.publicKey,
         protocol,
         market,
+        marketItems: marketItemsPda(program.programId, market),
         vaultAuthority,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -87,17 +94,17 @@ describe("realtime opinion market native SOL (E2E)", () => {
     await program.methods
       .openMarket()
       .accounts(accounts({
-        adminAuthority: admin.publicKey,
+        marketOperator: admin.publicKey,
         protocol,
         market,
       }))
       .rpc();
 
     const names = ["Alice", "Bob", "Carol"];
-    const stakes: { raw: number; effective: number }[] = [
-      { raw: 100_000_000, effective: 100_000_000 },
-      { raw: 200_000_000, effective: 300_000_000 },
-      { raw: 50_000_000, effective: 50_000_000 },
+    const stakes: { raw: number }[] = [
+      { raw: 100_000_000 },
+      { raw: 200_000_000 },
+      { raw: 50_000_000 },
     ];
 
     for (let i = 0; i < 3; i++) {
@@ -116,12 +123,12 @@ describe("realtime opinion market native SOL (E2E)", () => {
         keypair,
         itemIndex: i,
         rawStake: stakes[i].raw,
-        effectiveStake: stakes[i].effective,
+        effectiveStake: 0, // derived on-chain from the multiplier schedule
       });
     }
   });
 
-  it("runs full lifecycle with native SOL: place → close → settle → claim", async () => {
+  it("runs full lifecycle with native SOL: place → close → resolve → settle → claim", async () => {
     console.log("[E2E Native] Starting full lifecycle test");
 
     for (const p of participants) {
@@ -132,15 +139,20 @@ describe("realtime opinion market native SOL (E2E)", () => {
         .placePositionNative(
           p.itemIndex,
           new anchor.BN(p.rawStake),
-          new anchor.BN(p.effectiveStake)
+          new anchor.BN(p.rawStake)
         )
         .accounts(accounts({
           user: p.keypair.publicKey,
           protocol,
           market,
-          position: positionPda(program.programId, market, p.keypair.publicKey),
+          position: positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex),
           vault: vaultAddress,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
           systemProgram: anchor.web3.SystemProgram.programId,
+          referrerAccount: null,
+          marketReferral: null,
         }))
         .signers([p.keypair])
         .rpc();
@@ -150,7 +162,18 @@ describe("realtime opinion market native SOL (E2E)", () => {
     const totalRawStake = m.totalRawStake.toNumber();
     const totalEffectiveStake = m.totalEffectiveStake.toNumber();
     expect(totalRawStake).to.equal(350_000_000);
-    expect(totalEffectiveStake).to.equal(450_000_000);
+
+    // Early-bird schedule: each effective stake is between 1x and 2x raw stake
+    let summedEffectiveStake = 0;
+    for (const p of participants) {
+      const pos = await program.account.position.fetch(
+        positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex)
+      );
+      p.effectiveStake = pos.effectiveStake.toNumber();
+      expect(p.effectiveStake).to.be.within(p.rawStake, p.rawStake * 2);
+      summedEffectiveStake += p.effectiveStake;
+    }
+    expect(totalEffectiveStake).to.equal(summedEffectiveStake);
 
     const waitMs = (END_WINDOW_SEC + 15) * 1000;
     console.log(`[E2E Native] Waiting ${waitMs / 1000}s for market end_ts...`);
@@ -169,6 +192,20 @@ describe("realtime opinion market native SOL (E2E)", () => {
     m = await program.account.market.fetch(market);
     expect(m.status.closed !== undefined).to.be.true;
 
+    console.log(`[E2E Native] Instruction: resolve_market (winner = item ${WINNING_ITEM})`);
+    await program.methods
+      .resolveMarket(WINNING_ITEM)
+      .accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+      }))
+      .rpc();
+
+    m = await program.account.market.fetch(market);
+    expect(m.status.resolved !== undefined).to.be.true;
+    const winningEffectiveStake = m.effectiveStakePerItem[WINNING_ITEM].toNumber();
+
     console.log("[E2E Native] Instruction: settle_market_native");
     await program.methods
       .settleMarketNative()
@@ -178,6 +215,7 @@ describe("realtime opinion market native SOL (E2E)", () => {
         market,
         vault: vaultAddress,
         treasury: admin.publicKey,
+        creator: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       }))
       .rpc();
@@ -200,11 +238,11 @@ describe("realtime opinion market native SOL (E2E)", () => {
     for (const p of participants) {
       console.log(`[E2E Native] Instruction: claim_payout_native (${p.name})`);
       await program.methods
-        .claimPayoutNative()
+        .claimPayoutNative(false)
         .accounts(accounts({
           user: p.keypair.publicKey,
           market,
-          position: positionPda(program.programId, market, p.keypair.publicKey),
+          position: positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex),
           vault: vaultAddress,
           systemProgram: anchor.web3.SystemProgram.programId,
         }))
@@ -215,13 +253,13 @@ describe("realtime opinion market native SOL (E2E)", () => {
     for (let i = 0; i < participants.length; i++) {
       const p = participants[i];
       const pos = await program.account.position.fetch(
-        positionPda(program.programId, market, p.keypair.publicKey)
+        positionPda(program.programId, market, p.keypair.publicKey, p.itemIndex)
       );
       expect(pos.claimed).to.be.true;
 
-      const expectedPayout = Math.floor(
-        (Number(p.effectiveStake) * distributablePool) / totalEffectiveStake
-      );
+      const expectedPayout = p.itemIndex === WINNING_ITEM
+        ? Math.floor((Number(p.effectiveStake) * distributablePool) / winningEffectiveStake)
+        : 0;
       const balanceAfter = await connection.getBalance(p.keypair.publicKey);
       const received = balanceAfter - balancesBefore[i];
       expect(
@@ -230,6 +268,10 @@ describe("realtime opinion market native SOL (E2E)", () => {
       ).to.be.true;
     }
 
+    m = await program.account.market.fetch(market);
+    expect(m.positionsClaimed.toNumber()).to.equal(participants.length);
+    expect(m.totalClaimed.toNumber()).to.equal(distributablePool);
+
     const vaultAfter = await connection.getBalance(vaultAddress);
     expect(vaultAfter <= 5000, `vault remainder ${vaultAfter} should be <= 5000`).to.be.true;
   });
@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { accounts, admin, delay, marketPda, program, protocolPda, SUITE_DELAY_MS } from "./helpers";
+
+describe("resolve_market", () => {
+  before(async () => await delay(SUITE_DELAY_MS));
+
+  it("rejects when market is not closed", async () => {
+    // Last market is the settled native E2E market
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 1);
+
+    try {
+      await program.methods.resolveMarket(0).accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string; errorCode?: { code?: string; number?: number } };
+      const msg = (err?.message ?? String(e)) + (err?.errorCode?.code ?? "") + String(err?.errorCode?.number ?? "");
+      expect(msg).to.match(/InvalidMarketState|6003|required state/i);
+    }
+  });
+
+  it("rejects non-admin signer", async () => {
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 1);
+    const other = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods.resolveMarket(0).accounts(accounts({
+        marketOperator: other.publicKey,
+        protocol,
+        market,
+      })).signers([other]).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("Unauthorized");
+    }
+  });
+});
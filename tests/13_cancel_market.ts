@@ -0,0 +1,205 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  delay,
+  ensureMintConfig,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  TWO_ITEMS,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const RAW_STAKE = 10_000_000;
+
+describe("cancel_market", () => {
+  before(async () => await delay(SUITE_DELAY_MS));
+
+  it("cancels a draft market", async () => {
+    // Second market from create_market is still Draft (future start_ts)
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 3);
+
+    await program.methods.cancelMarket().accounts(accounts({
+      marketOperator: admin.publicKey,
+      protocol,
+      market,
+    })).rpc();
+
+    const m = await program.account.market.fetch(market);
+    expect(m.status.cancelled !== undefined).to.be.true;
+  });
+
+  it("rejects cancelling a settled market", async () => {
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 1);
+
+    try {
+      await program.methods.cancelMarket().accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string; errorCode?: { code?: string; number?: number } };
+      const msg = (err?.message ?? String(e)) + (err?.errorCode?.code ?? "") + String(err?.errorCode?.number ?? "");
+      expect(msg).to.match(/InvalidMarketState|6003|required state/i);
+    }
+  });
+
+  it("rejects refund when market is not cancelled", async () => {
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 1);
+    const wallet = program.provider.wallet;
+
+    try {
+      await program.methods.refundPositionNative().accounts(accounts({
+        user: wallet.publicKey,
+        market,
+        position: positionPda(program.programId, market, wallet.publicKey, 0),
+        vault: vaultAuthorityPda(program.programId, market),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string; errorCode?: { code?: string; number?: number } };
+      const msg = (err?.message ?? String(e)) + (err?.errorCode?.code ?? "") + String(err?.errorCode?.number ?? "");
+      expect(msg).to.match(/InvalidMarketState|6003|required state|position/i);
+    }
+  });
+
+  it("refunds the raw stake of a cancelled market once and closes the position", async () => {
+    const protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    const vault = vaultAuthorityPda(program.programId, market);
+    const user = anchor.web3.Keypair.generate();
+    const position = positionPda(program.programId, market, user.publicKey, 0);
+
+    await program.methods
+      .createMarketNative(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 60),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority: vault,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+    await program.methods
+      .placePositionNative(0, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+    const closePosition = () =>
+      program.methods.closePosition().accounts(accounts({
+        signer: admin.publicKey,
+        market,
+        position,
+        user: user.publicKey,
+      })).rpc();
+
+    // A position that can still be refunded keeps its account
+    try {
+      await closePosition();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("PositionNotClosable");
+    }
+
+    await program.methods.cancelMarket().accounts(accounts({
+      marketOperator: admin.publicKey,
+      protocol,
+      market,
+    })).rpc();
+
+    const refund = () =>
+      program.methods.refundPositionNative().accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        vault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })).signers([user]).rpc();
+
+    // The admin wallet pays the transaction fee, so the user's balance moves by the refund only
+    const before = await connection.getBalance(user.publicKey);
+    await refund();
+    expect((await connection.getBalance(user.publicKey)) - before).to.equal(RAW_STAKE);
+    expect((await program.account.position.fetch(position)).refunded).to.be.true;
+    expect((await program.account.market.fetch(market)).positionsRefunded.toNumber()).to.equal(1);
+
+    try {
+      await refund();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("AlreadyRefunded");
+    }
+    expect(await connection.getBalance(user.publicKey)).to.equal(before + RAW_STAKE);
+
+    // Anyone can then close the refunded position; its rent goes back to the user
+    const rent = await connection.getBalance(position);
+    await closePosition();
+    expect(await connection.getAccountInfo(position)).to.be.null;
+    expect(await connection.getBalance(user.publicKey)).to.equal(before + RAW_STAKE + rent);
+    expect((await program.account.market.fetch(market)).positionsClosed.toNumber()).to.equal(1);
+  });
+});
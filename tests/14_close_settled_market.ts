@@ -0,0 +1,285 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  delay,
+  ensureMintConfig,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  mintConfigPda,
+  positionPda,
+  program,
+  protocolPda,
+  TWO_ITEMS,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const RAW_STAKE = 1_000_000;
+
+describe("close_settled_market", () => {
+  before(async () => await delay(SUITE_DELAY_MS));
+
+  it("rejects when market is not settled", async () => {
+    // Market cancelled by cancel_market suite
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 4);
+    const m = await program.account.market.fetch(market);
+    const treasuryAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      admin.payer,
+      m.tokenMint,
+      admin.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    try {
+      await program.methods.closeSettledMarket().accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        creator: m.creator,
+        vaultAuthority: vaultAuthorityPda(program.programId, market),
+        vault: m.vault,
+        treasuryTokenAccount: treasuryAta.address,
+        tokenMint: m.tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string; errorCode?: { code?: string; number?: number } };
+      const msg = (err?.message ?? String(e)) + (err?.errorCode?.code ?? "") + String(err?.errorCode?.number ?? "");
+      expect(msg).to.match(/InvalidMarketState|6003|required state/i);
+    }
+  });
+
+  it("returns position rent to the user and market rent to the creator", async () => {
+    const protocol = protocolPda(program.programId);
+    const tokenMint = await createMint(
+      connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    await ensureMintConfig(tokenMint);
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    const marketItems = marketItemsPda(program.programId, market);
+    const vaultAuthority = vaultAuthorityPda(program.programId, market);
+    const vault = getAssociatedTokenAddressSync(
+      tokenMint,
+      vaultAuthority,
+      true,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const treasuryAta = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        admin.payer,
+        tokenMint,
+        admin.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      )
+    ).address;
+
+    await program.methods
+      .createMarket(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 8),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems,
+        vaultAuthority,
+        vault,
+        tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        creatorTokenAccount: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+    const userAta = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(connection, admin.payer, tokenMint, userAta, admin.publicKey, RAW_STAKE);
+
+    const position = positionPda(program.programId, market, user.publicKey, 0);
+    await program.methods
+      .placePosition(0, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        tokenMint,
+        userTokenAccount: userAta,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+    await delay(8000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .resolveMarket(0)
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+    await program.methods
+      .settleMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vaultAuthority,
+        vault,
+        tokenMint,
+        treasury: admin.publicKey,
+        treasuryTokenAccount: treasuryAta,
+        creator: admin.publicKey,
+        creatorTokenAccount: treasuryAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    // The admin wallet pays the transaction fee, so the user's lamports move by the rent only
+    const positionRent = await connection.getBalance(position);
+    const userLamportsBefore = await connection.getBalance(user.publicKey);
+    await program.methods
+      .claimPayout(true)
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        vaultAuthority,
+        vault,
+        userTokenAccount: userAta,
+        tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }))
+      .signers([user])
+      .rpc();
+
+    expect(positionRent).to.be.greaterThan(0);
+    expect(await connection.getAccountInfo(position)).to.be.null;
+    expect((await connection.getBalance(user.publicKey)) - userLamportsBefore).to.equal(positionRent);
+    const { distributablePool } = await program.account.market.fetch(market);
+    const userTokens = await connection.getTokenAccountBalance(userAta);
+    expect(Number(userTokens.value.amount)).to.equal(distributablePool.toNumber());
+
+    const rent =
+      (await connection.getBalance(vault)) +
+      (await connection.getBalance(market)) +
+      (await connection.getBalance(marketItems));
+    const creatorBefore = await connection.getBalance(admin.publicKey);
+    const signature = await program.methods
+      .closeSettledMarket()
+      .accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+        marketItems,
+        creator: admin.publicKey,
+        vaultAuthority,
+        vault,
+        treasuryTokenAccount: treasuryAta,
+        tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }))
+      .rpc();
+
+    for (const account of [vault, market, marketItems]) {
+      expect(await connection.getAccountInfo(account)).to.be.null;
+    }
+    // The creator is also the fee payer here, so add the fee back before comparing
+    await connection.confirmTransaction(signature, "confirmed");
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(
+      (await connection.getBalance(admin.publicKey)) - creatorBefore + (tx?.meta?.fee ?? 0)
+    ).to.equal(rent);
+  });
+});
@@ -0,0 +1,209 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  cpiEvents,
+  DEFAULT_CLAIM_WINDOW_SECS,
+  delay,
+  ensureMintConfig,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  TWO_ITEMS,
+  updateProtocol,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const CLAIM_WINDOW_SECS = 2;
+const RAW_STAKE = 10_000_000;
+
+describe("sweep_market", () => {
+  before(async () => await delay(SUITE_DELAY_MS));
+
+  after(async () => await updateProtocol({ claimWindowSecs: DEFAULT_CLAIM_WINDOW_SECS }));
+
+  it("rejects before the claim deadline", async () => {
+    // Settled native E2E market; cancel_market and close_settled_market each create one after it
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 3);
+    const m = await program.account.market.fetch(market);
+    expect(m.claimDeadlineTs.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods.sweepMarketNative().accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vault: vaultAuthorityPda(program.programId, market),
+        treasury: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("ClaimDeadlineNotReached");
+    }
+  });
+
+  it("rejects late claims, sweeps the vault and closes the expired position", async () => {
+    const protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+    await updateProtocol({ claimWindowSecs: CLAIM_WINDOW_SECS });
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    const vault = vaultAuthorityPda(program.programId, market);
+    const user = anchor.web3.Keypair.generate();
+    const position = positionPda(program.programId, market, user.publicKey, 0);
+
+    await program.methods
+      .createMarketNative(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 8),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority: vault,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+    await program.methods
+      .placePositionNative(0, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+    await delay(8000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .resolveMarket(0)
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    await program.methods
+      .settleMarketNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vault,
+        treasury,
+        creator: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    // Deadline is snapshotted from the shortened window
+    const settled = await program.account.market.fetch(market);
+    const settledAt = settled.claimDeadlineTs.toNumber() - CLAIM_WINDOW_SECS;
+    expect(settledAt).to.be.greaterThanOrEqual(now);
+
+    await delay((CLAIM_WINDOW_SECS + 3) * 1000);
+
+    try {
+      await program.methods
+        .claimPayoutNative(false)
+        .accounts(accounts({
+          user: user.publicKey,
+          protocol,
+          market,
+          position,
+          vault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .signers([user])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("ClaimDeadlinePassed");
+    }
+
+    const unclaimed = await connection.getBalance(vault);
+    expect(unclaimed).to.be.greaterThanOrEqual(settled.distributablePool.toNumber());
+
+    const signature = await program.methods
+      .sweepMarketNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vault,
+        treasury,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    expect(await connection.getBalance(vault)).to.equal(0);
+    const [swept] = (await cpiEvents(signature)).filter((e) => e.name === "marketSwept");
+    expect(swept.data.market.equals(market)).to.be.true;
+    expect(swept.data.amount.toNumber()).to.equal(unclaimed);
+    expect((await program.account.position.fetch(position)).claimed).to.be.false;
+
+    // The expired position can no longer claim, so anyone can close it for the user
+    const rent = await connection.getBalance(position);
+    const userBefore = await connection.getBalance(user.publicKey);
+    await program.methods
+      .closePosition()
+      .accounts(accounts({ signer: admin.publicKey, market, position, user: user.publicKey }))
+      .rpc();
+    expect(await connection.getAccountInfo(position)).to.be.null;
+    expect((await connection.getBalance(user.publicKey)) - userBefore).to.equal(rent);
+  });
+});
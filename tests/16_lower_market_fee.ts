@@ -0,0 +1,42 @@
+import { expect } from "chai";
+import { accounts, admin, delay, marketPda, program, protocolPda, SUITE_DELAY_MS } from "./helpers";
+
+describe("lower_market_fee", () => {
+  before(async () => await delay(SUITE_DELAY_MS));
+
+  it("lowers the fee snapshotted on an open market", async () => {
+    // First market from create_market, opened by edit_market
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 7);
+    const before = await program.account.market.fetch(market);
+
+    await program.methods.lowerMarketFee(before.feeBps - 100).accounts(accounts({
+      feeManager: admin.publicKey,
+      protocol,
+      market,
+    })).rpc();
+
+    const m = await program.account.market.fetch(market);
+    expect(m.feeBps).to.equal(before.feeBps - 100);
+  });
+
+  it("rejects raising the fee", async () => {
+    const protocol = protocolPda(program.programId);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount - 7);
+    const m = await program.account.market.fetch(market);
+
+    try {
+      await program.methods.lowerMarketFee(m.feeBps + 1).accounts(accounts({
+        feeManager: admin.publicKey,
+        protocol,
+        market,
+      })).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("FeeIncreaseNotAllowed");
+    }
+  });
+});
@@ -0,0 +1,205 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  ATTESTED_SCHEDULE,
+  attestationIx,
+  attestationMessage,
+  attestationReceiptPda,
+  connection,
+  delay,
+  ensureMintConfig,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  positionPda,
+  program,
+  protocolPda,
+  scoringAuthority,
+  TWO_ITEMS,
+  updateProtocol,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+describe("place_position (attested)", () => {
+  let protocol: anchor.web3.PublicKey;
+  let market: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let now: number;
+
+  const placeAccounts = (item: number, receipt: anchor.web3.PublicKey | null) =>
+    accounts({
+      user: user.publicKey,
+      protocol,
+      market,
+      position: positionPda(program.programId, market, user.publicKey, item),
+      vault,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      attestationReceipt: receipt,
+      mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+      systemProgram: anchor.web3.SystemProgram.programId,
+      referrerAccount: null,
+      marketReferral: null,
+    });
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+
+    const proto = await program.account.protocol.fetch(protocol);
+    await updateProtocol({ scoringAuthority: scoringAuthority.publicKey });
+
+    const slot = await connection.getSlot();
+    now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+
+    market = marketPda(program.programId, proto.marketCount.toNumber());
+    vault = vaultAuthorityPda(program.programId, market);
+
+    await program.methods
+      .createMarketNative(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 3600),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        ATTESTED_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority: vault,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+  });
+
+  it("rejects a position without an attestation", async () => {
+    try {
+      await program.methods
+        .placePositionNative(0, new anchor.BN(1_000_000), new anchor.BN(0))
+        .accounts(placeAccounts(0, null))
+        .signers([user])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MissingAttestation");
+    }
+  });
+
+  it("rejects an expired attestation", async () => {
+    const message = attestationMessage(market, user.publicKey, 0, 1_000_000, 1_500_000, now - 1);
+    try {
+      await program.methods
+        .placePositionNative(0, new anchor.BN(1_000_000), new anchor.BN(0))
+        .accounts(placeAccounts(0, attestationReceiptPda(program.programId, message)))
+        .preInstructions([attestationIx(message)])
+        .signers([user])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("AttestationExpired");
+    }
+  });
+
+  it("uses the attested effective stake and burns the attestation", async () => {
+    const rawStake = 1_000_000;
+    const effectiveStake = 1_500_000;
+    const message = attestationMessage(market, user.publicKey, 1, rawStake, effectiveStake, now + 600);
+    const receipt = attestationReceiptPda(program.programId, message);
+
+    await program.methods
+      .placePositionNative(1, new anchor.BN(rawStake), new anchor.BN(effectiveStake))
+      .accounts(placeAccounts(1, receipt))
+      .preInstructions([attestationIx(message)])
+      .signers([user])
+      .rpc();
+
+    const pos = await program.account.position.fetch(
+      positionPda(program.programId, market, user.publicKey, 1)
+    );
+    expect(pos.effectiveStake.toNumber()).to.equal(effectiveStake);
+
+    const receiptInfo = await connection.getAccountInfo(receipt);
+    expect(receiptInfo).to.not.be.null;
+    expect(receiptInfo!.owner.equals(program.programId)).to.be.true;
+    const data = await program.account.attestationReceipt.fetch(receipt);
+    expect(data.payer.equals(user.publicKey)).to.be.true;
+    expect(data.expiryTs.toNumber()).to.equal(now + 600);
+  });
+
+  it("accepts a pre-funded receipt and closes it once the attestation expires", async () => {
+    const slot = await connection.getSlot();
+    const current = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const message = attestationMessage(market, user.publicKey, 0, 1_000_000, 1_000_000, current + 3);
+    const receipt = attestationReceiptPda(program.programId, message);
+
+    // Anyone can send lamports to the receipt address ahead of the placement
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: receipt,
+          lamports: 1_000_000,
+        })
+      )
+    );
+
+    await program.methods
+      .placePositionNative(0, new anchor.BN(1_000_000), new anchor.BN(1_000_000))
+      .accounts(placeAccounts(0, receipt))
+      .preInstructions([attestationIx(message)])
+      .signers([user])
+      .rpc();
+    expect((await program.account.attestationReceipt.fetch(receipt)).payer.equals(user.publicKey))
+      .to.be.true;
+
+    const closeAccounts = accounts({
+      signer: admin.publicKey,
+      attestationReceipt: receipt,
+      payer: user.publicKey,
+    });
+    try {
+      await program.methods.closeAttestationReceipt().accounts(closeAccounts).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("AttestationNotExpired");
+    }
+
+    await delay(5000);
+    const rent = await connection.getBalance(receipt);
+    const userBefore = await connection.getBalance(user.publicKey);
+    await program.methods.closeAttestationReceipt().accounts(closeAccounts).rpc();
+
+    expect(await connection.getAccountInfo(receipt)).to.be.null;
+    expect((await connection.getBalance(user.publicKey)) - userBefore).to.equal(rent);
+  });
+});
@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createInitializePermanentDelegateInstruction,
+  ExtensionType,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  createToken2022Mint,
+  createTransferFeeMint,
+  delay,
+  ensureMintConfig,
+  fundedUser,
+  mintConfigPda,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  positionPda,
+  program,
+  protocolPda,
+  TRANSFER_FEE_BPS,
+  TWO_ITEMS,
+  U64_MAX,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const CREATOR_BOND = 5_000_000;
+
+describe("token-2022 stake mints", () => {
+  let protocol: anchor.web3.PublicKey;
+
+  const createMarketIx = async (
+    tokenMint: anchor.web3.PublicKey,
+    market: anchor.web3.PublicKey,
+    bonded?: { creator: anchor.web3.Keypair; creatorTokenAccount: anchor.web3.PublicKey }
+  ) => {
+    await ensureMintConfig(tokenMint);
+    if (bonded) {
+      await program.methods
+        .updateMintConfig(true, new anchor.BN(1), U64_MAX, new anchor.BN(CREATOR_BOND))
+        .accounts(accounts({
+          adminAuthority: admin.publicKey,
+          protocol,
+          mintConfig: mintConfigPda(program.programId, tokenMint),
+        }))
+        .rpc();
+    }
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const vaultAuthority = vaultAuthorityPda(program.programId, market);
+
+    return program.methods
+      .createMarket(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 3600),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: bonded?.creator.publicKey ?? admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority,
+        vault: getAssociatedTokenAddressSync(
+          tokenMint,
+          vaultAuthority,
+          true,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID
+        ),
+        tokenMint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        creatorTokenAccount: bonded?.creatorTokenAccount ?? null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .signers(bonded ? [bonded.creator] : []);
+  };
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+  });
+
+  it("rejects a mint with a permanent delegate", async () => {
+    const tokenMint = await createToken2022Mint([ExtensionType.PermanentDelegate], (mint) => [
+      createInitializePermanentDelegateInstruction(mint, admin.publicKey, TOKEN_2022_PROGRAM_ID),
+    ]);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+
+    try {
+      await (await createMarketIx(tokenMint, marketPda(program.programId, marketCount))).rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("UnsupportedMintExtension");
+    }
+  });
+
+  it("credits the amount received after the transfer fee", async () => {
+    const tokenMint = await createTransferFeeMint();
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    await (await createMarketIx(tokenMint, market)).rpc();
+
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const { user, ata: userAta } = await fundedUser(tokenMint);
+
+    const rawStake = 1_000_000;
+    const expectedCredit = rawStake - (rawStake * TRANSFER_FEE_BPS) / 10_000;
+    const m = await program.account.market.fetch(market);
+    const position = positionPda(program.programId, market, user.publicKey, 0);
+
+    await program.methods
+      .placePosition(0, new anchor.BN(rawStake), new anchor.BN(expectedCredit))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        tokenMint,
+        userTokenAccount: userAta,
+        vault: m.vault,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, tokenMint),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+    const pos = await program.account.position.fetch(position);
+    expect(pos.rawStake.toNumber()).to.equal(expectedCredit);
+    expect(pos.effectiveStake.toNumber()).to.equal(expectedCredit);
+
+    const after = await program.account.market.fetch(market);
+    expect(after.totalRawStake.toNumber()).to.equal(expectedCredit);
+  });
+
+  it("records the creator bond received after the transfer fee", async () => {
+    const tokenMint = await createTransferFeeMint();
+    const { user: creator, ata: creatorTokenAccount } = await fundedUser(tokenMint);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+
+    await (await createMarketIx(tokenMint, market, { creator, creatorTokenAccount })).rpc();
+
+    const expectedBond = CREATOR_BOND - (CREATOR_BOND * TRANSFER_FEE_BPS) / 10_000;
+    const m = await program.account.market.fetch(market);
+    expect(m.creatorBond.toNumber()).to.equal(expectedBond);
+
+    const vault = await getAccount(connection, m.vault, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(vault.amount)).to.equal(expectedBond);
+  });
+});
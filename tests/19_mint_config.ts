@@ -0,0 +1,142 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  delay,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  mintConfigPda,
+  program,
+  protocolPda,
+  TWO_ITEMS,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+describe("mint_config", () => {
+  let protocol: anchor.web3.PublicKey;
+  let tokenMint: anchor.web3.PublicKey;
+  let mintConfig: anchor.web3.PublicKey;
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    tokenMint = await createMint(
+      connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    mintConfig = mintConfigPda(program.programId, tokenMint);
+  });
+
+  it("rejects a zero minimum stake", async () => {
+    try {
+      await program.methods
+        .createMintConfig(tokenMint, new anchor.BN(0), new anchor.BN(1_000_000), new anchor.BN(0))
+        .accounts(accounts({
+          adminAuthority: admin.publicKey,
+          protocol,
+          mintConfig,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidMintConfig");
+    }
+  });
+
+  it("creates an enabled mint config", async () => {
+    await program.methods
+      .createMintConfig(tokenMint, new anchor.BN(1_000), new anchor.BN(1_000_000), new anchor.BN(0))
+      .accounts(accounts({
+        adminAuthority: admin.publicKey,
+        protocol,
+        mintConfig,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    const config = await program.account.mintConfig.fetch(mintConfig);
+    expect(config.mint.equals(tokenMint)).to.be.true;
+    expect(config.enabled).to.be.true;
+    expect(config.minStake.toNumber()).to.equal(1_000);
+    expect(config.maxStake.toNumber()).to.equal(1_000_000);
+  });
+
+  it("rejects non-admin updates", async () => {
+    const other = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .updateMintConfig(false, new anchor.BN(1_000), new anchor.BN(1_000_000), new anchor.BN(0))
+        .accounts(accounts({ adminAuthority: other.publicKey, protocol, mintConfig }))
+        .signers([other])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.match(/Unauthorized|ConstraintHasOne|has_one/i);
+    }
+  });
+
+  it("rejects markets on a disabled mint", async () => {
+    await program.methods
+      .updateMintConfig(false, new anchor.BN(1_000), new anchor.BN(1_000_000), new anchor.BN(0))
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol, mintConfig }))
+      .rpc();
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const market = marketPda(program.programId, marketCount);
+    const vaultAuthority = vaultAuthorityPda(program.programId, market);
+
+    try {
+      await program.methods
+        .createMarket(
+          new anchor.BN(now - 60),
+          new anchor.BN(now + 3600),
+          itemsHash(TWO_ITEMS),
+          2,
+          TWO_ITEMS,
+          FLAT_SCHEDULE,
+          0
+        )
+        .accounts(accounts({
+          creator: admin.publicKey,
+          protocol,
+          market,
+          marketItems: marketItemsPda(program.programId, market),
+          vaultAuthority,
+          vault: getAssociatedTokenAddressSync(tokenMint, vaultAuthority, true),
+          tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          mintConfig,
+          creatorTokenAccount: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MintNotAllowed");
+    }
+  });
+});
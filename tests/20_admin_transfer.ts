@@ -0,0 +1,92 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { accounts, admin, delay, program, protocolPda, SUITE_DELAY_MS } from "./helpers";
+
+describe("admin transfer", () => {
+  let protocol: anchor.web3.PublicKey;
+  const nextAdmin = anchor.web3.Keypair.generate();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+  });
+
+  it("proposes and cancels a new admin", async () => {
+    await program.methods
+      .proposeAdmin(nextAdmin.publicKey)
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+
+    let p = await program.account.protocol.fetch(protocol);
+    expect(p.pendingAdmin!.equals(nextAdmin.publicKey)).to.be.true;
+
+    await program.methods
+      .cancelAdminProposal()
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+
+    p = await program.account.protocol.fetch(protocol);
+    expect(p.pendingAdmin).to.be.null;
+    expect(p.adminAuthority.equals(admin.publicKey)).to.be.true;
+  });
+
+  it("rejects accepting with no pending proposal", async () => {
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts(accounts({ pendingAdmin: nextAdmin.publicKey, protocol }))
+        .signers([nextAdmin])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("NoPendingAdmin");
+    }
+  });
+
+  it("rejects acceptance by anyone but the proposed admin", async () => {
+    await program.methods
+      .proposeAdmin(nextAdmin.publicKey)
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+
+    const other = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts(accounts({ pendingAdmin: other.publicKey, protocol }))
+        .signers([other])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("Unauthorized");
+    }
+  });
+
+  it("hands over control once the proposed admin accepts, and back again", async () => {
+    await program.methods
+      .acceptAdmin()
+      .accounts(accounts({ pendingAdmin: nextAdmin.publicKey, protocol }))
+      .signers([nextAdmin])
+      .rpc();
+
+    let p = await program.account.protocol.fetch(protocol);
+    expect(p.adminAuthority.equals(nextAdmin.publicKey)).to.be.true;
+    expect(p.pendingAdmin).to.be.null;
+
+    // Restore the test wallet as admin for later suites
+    await program.methods
+      .proposeAdmin(admin.publicKey)
+      .accounts(accounts({ adminAuthority: nextAdmin.publicKey, protocol }))
+      .signers([nextAdmin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts(accounts({ pendingAdmin: admin.publicKey, protocol }))
+      .rpc();
+
+    p = await program.account.protocol.fetch(protocol);
+    expect(p.adminAuthority.equals(admin.publicKey)).to.be.true;
+  });
+});
@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { accounts, admin, cpiEvents, delay, PAUSE_ALL, program, protocolPda, SUITE_DELAY_MS } from "./helpers";
+
+describe("roles", () => {
+  let protocol: anchor.web3.PublicKey;
+  const pauser = anchor.web3.Keypair.generate();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+  });
+
+  it("grants a role and records the previous holder", async () => {
+    const signature = await program.methods
+      .grantRole({ pauser: {} }, pauser.publicKey)
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+
+    const p = await program.account.protocol.fetch(protocol);
+    expect(p.pauser.equals(pauser.publicKey)).to.be.true;
+
+    const event = (await cpiEvents(signature)).find((e) => e.name === "roleGranted");
+    expect(event).to.not.be.undefined;
+    expect(event!.data.previousAuthority.equals(admin.publicKey)).to.be.true;
+    expect(event!.data.authority.equals(pauser.publicKey)).to.be.true;
+    expect(event!.data.grantedBy.equals(admin.publicKey)).to.be.true;
+  });
+
+  it("only the role holder can use it", async () => {
+    try {
+      await program.methods
+        .setPauseFlags(PAUSE_ALL)
+        .accounts(accounts({ pauser: admin.publicKey, protocol }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("Unauthorized");
+    }
+
+    await program.methods
+      .setPauseFlags(PAUSE_ALL)
+      .accounts(accounts({ pauser: pauser.publicKey, protocol }))
+      .signers([pauser])
+      .rpc();
+    await program.methods
+      .setPauseFlags(0)
+      .accounts(accounts({ pauser: pauser.publicKey, protocol }))
+      .signers([pauser])
+      .rpc();
+  });
+
+  it("rejects grants from anyone but the admin", async () => {
+    try {
+      await program.methods
+        .grantRole({ feeManager: {} }, pauser.publicKey)
+        .accounts(accounts({ adminAuthority: pauser.publicKey, protocol }))
+        .signers([pauser])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("Unauthorized");
+    }
+  });
+
+  it("revokes a role", async () => {
+    await program.methods
+      .revokeRole({ pauser: {} })
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+
+    const p = await program.account.protocol.fetch(protocol);
+    expect(p.pauser.equals(anchor.web3.PublicKey.default)).to.be.true;
+
+    try {
+      await program.methods
+        .revokeRole({ pauser: {} })
+        .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("RoleNotAssigned");
+    }
+
+    // Restore the test wallet as pauser for later suites
+    await program.methods
+      .grantRole({ pauser: {} }, admin.publicKey)
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol }))
+      .rpc();
+  });
+});
@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  createNativeMarket,
+  delay,
+  ensureMintConfig,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  PAUSE_ALL,
+  PAUSE_DEPOSITS,
+  PAUSE_MARKET_CREATION,
+  positionPda,
+  program,
+  protocolPda,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+describe("pause flags", () => {
+  let protocol: anchor.web3.PublicKey;
+  let market: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+
+  const setPauseFlags = (flags: number) =>
+    program.methods
+      .setPauseFlags(flags)
+      .accounts(accounts({ pauser: admin.publicKey, protocol }))
+      .rpc();
+
+  const placePosition = () =>
+    program.methods
+      .placePositionNative(0, new anchor.BN(1_000_000), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, 0),
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+    await setPauseFlags(0);
+
+    market = await createNativeMarket();
+    vault = vaultAuthorityPda(program.programId, market);
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+  });
+
+  after(async () => await setPauseFlags(0));
+
+  it("rejects unknown flag bits", async () => {
+    try {
+      await setPauseFlags(PAUSE_ALL + 1);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidPauseFlags");
+    }
+  });
+
+  it("pausing deposits blocks positions only", async () => {
+    await setPauseFlags(PAUSE_DEPOSITS);
+
+    try {
+      await placePosition();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("ProtocolPaused");
+    }
+
+    // Market creation is still allowed
+    await createNativeMarket();
+  });
+
+  it("pausing market creation leaves deposits open", async () => {
+    await setPauseFlags(PAUSE_MARKET_CREATION);
+
+    await placePosition();
+
+    try {
+      await createNativeMarket();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("ProtocolPaused");
+    }
+  });
+});
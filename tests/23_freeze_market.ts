@@ -0,0 +1,139 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  createNativeMarket,
+  delay,
+  ensureMintConfig,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+describe("freeze_market", () => {
+  let protocol: anchor.web3.PublicKey;
+  let market: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+
+  const placePosition = () =>
+    program.methods
+      .placePositionNative(0, new anchor.BN(1_000_000), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, 0),
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+
+    market = await createNativeMarket();
+    vault = vaultAuthorityPda(program.programId, market);
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+  });
+
+  it("rejects freezing by anyone but the pauser", async () => {
+    const other = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .freezeMarket()
+        .accounts(accounts({ pauser: other.publicKey, protocol, market }))
+        .signers([other])
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("Unauthorized");
+    }
+  });
+
+  it("blocks positions on a frozen market", async () => {
+    await program.methods
+      .freezeMarket()
+      .accounts(accounts({ pauser: admin.publicKey, protocol, market }))
+      .rpc();
+    expect((await program.account.market.fetch(market)).frozen).to.be.true;
+
+    try {
+      await placePosition();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MarketFrozen");
+    }
+  });
+
+  it("blocks sweeping the vault of a frozen market", async () => {
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    try {
+      await program.methods
+        .sweepMarketNative()
+        .accounts(accounts({
+          signer: admin.publicKey,
+          protocol,
+          market,
+          vault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MarketFrozen");
+    }
+  });
+
+  it("accepts positions again once unfrozen", async () => {
+    await program.methods
+      .unfreezeMarket()
+      .accounts(accounts({ pauser: admin.publicKey, protocol, market }))
+      .rpc();
+
+    await placePosition();
+    expect((await program.account.market.fetch(market)).totalRawStake.toNumber()).to.equal(1_000_000);
+
+    try {
+      await program.methods
+        .unfreezeMarket()
+        .accounts(accounts({ pauser: admin.publicKey, protocol, market }))
+        .rpc();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MarketNotFrozen");
+    }
+  });
+});
@@ -0,0 +1,160 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  DEFAULT_EMERGENCY_TIMEOUT_SECS,
+  delay,
+  ensureMintConfig,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  TWO_ITEMS,
+  updateProtocol,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const EMERGENCY_TIMEOUT_SECS = 2;
+const RAW_STAKE = 5_000_000;
+
+describe("emergency_withdraw", () => {
+  let protocol: anchor.web3.PublicKey;
+  let market: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let endTs: number;
+
+  const withdraw = () =>
+    program.methods
+      .emergencyWithdrawNative()
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, 0),
+        vault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+    await updateProtocol({ emergencyTimeoutSecs: EMERGENCY_TIMEOUT_SECS });
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    endTs = now + 4;
+
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    market = marketPda(program.programId, marketCount);
+    vault = vaultAuthorityPda(program.programId, market);
+
+    await program.methods
+      .createMarketNative(
+        new anchor.BN(now - 60),
+        new anchor.BN(endTs),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority: vault,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+
+    await program.methods
+      .placePositionNative(0, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, 0),
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+  });
+
+  after(async () => await updateProtocol({ emergencyTimeoutSecs: DEFAULT_EMERGENCY_TIMEOUT_SECS }));
+
+  it("rejects withdrawal before the timeout", async () => {
+    try {
+      await withdraw();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("EmergencyWithdrawalNotAvailable");
+    }
+  });
+
+  it("returns the raw stake and cancels the unsettled market", async () => {
+    // Wait until end_ts + timeout has passed on-chain
+    for (;;) {
+      const slot = await connection.getSlot();
+      const now = (await connection.getBlockTime(slot)) ?? 0;
+      if (now > endTs + EMERGENCY_TIMEOUT_SECS) break;
+      await delay(1000);
+    }
+
+    const vaultBefore = await connection.getBalance(vault);
+    await withdraw();
+    const vaultAfter = await connection.getBalance(vault);
+    expect(vaultBefore - vaultAfter).to.equal(RAW_STAKE);
+
+    const m = await program.account.market.fetch(market);
+    expect(m.status).to.deep.equal({ cancelled: {} });
+
+    const pos = await program.account.position.fetch(
+      positionPda(program.programId, market, user.publicKey, 0)
+    );
+    expect(pos.refunded).to.be.true;
+
+    try {
+      await withdraw();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("AlreadyRefunded");
+    }
+  });
+});
@@ -0,0 +1,75 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  createNativeMarket,
+  delay,
+  ensureMintConfig,
+  marketItemsPda,
+  NATIVE_STAKE_MINT,
+  program,
+  protocolPda,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+describe("close_empty_market", () => {
+  let protocol: anchor.web3.PublicKey;
+
+  const closeEmpty = async (market: anchor.web3.PublicKey) => {
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    return program.methods
+      .closeEmptyMarketNative()
+      .accounts(accounts({
+        marketOperator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        creator: admin.publicKey,
+        vault: vaultAuthorityPda(program.programId, market),
+        treasury,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+  };
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+  });
+
+  it("rejects an open market", async () => {
+    const market = await createNativeMarket();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    try {
+      await closeEmpty(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidMarketState");
+    }
+  });
+
+  it("closes a market nobody joined and returns rent to the creator", async () => {
+    const market = await createNativeMarket();
+    const marketItems = marketItemsPda(program.programId, market);
+    const rent =
+      (await connection.getBalance(market)) + (await connection.getBalance(marketItems));
+
+    const before = await connection.getBalance(admin.publicKey);
+    await closeEmpty(market);
+    const after = await connection.getBalance(admin.publicKey);
+
+    // Creator pays the tx fee, so allow a small margin
+    expect(after - before).to.be.greaterThan(rent - 10_000);
+    expect(await connection.getAccountInfo(market)).to.be.null;
+    expect(await connection.getAccountInfo(marketItems)).to.be.null;
+  });
+});
@@ -0,0 +1,320 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  DEFAULT_EMERGENCY_TIMEOUT_SECS,
+  createNativeMarket,
+  delay,
+  ensureMintConfig,
+  marketItemsPda,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  U64_MAX,
+  updateProtocol,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const CREATOR_BOND = 50_000_000;
+const EMERGENCY_TIMEOUT_SECS = 2;
+const RAW_STAKE = 5_000_000;
+
+describe("creator bonds", () => {
+  let protocol: anchor.web3.PublicKey;
+  let mintConfig: anchor.web3.PublicKey;
+  let creator: anchor.web3.Keypair;
+
+  const setCreatorBond = (bond: number) =>
+    program.methods
+      .updateMintConfig(true, new anchor.BN(1), U64_MAX, new anchor.BN(bond))
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol, mintConfig }))
+      .rpc();
+
+  const release = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .releaseCreatorBondNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        creator: creator.publicKey,
+        vault: vaultAuthorityPda(program.programId, market),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+  const slash = async (market: anchor.web3.PublicKey) => {
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    return program.methods
+      .slashCreatorBondNative()
+      .accounts(accounts({
+        adminAuthority: admin.publicKey,
+        protocol,
+        market,
+        vault: vaultAuthorityPda(program.programId, market),
+        treasury,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+  };
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    mintConfig = mintConfigPda(program.programId, NATIVE_STAKE_MINT);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+
+    creator = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: creator.publicKey,
+          lamports: 500_000_000,
+        })
+      )
+    );
+  });
+
+  after(async () => {
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+    await updateProtocol({ emergencyTimeoutSecs: DEFAULT_EMERGENCY_TIMEOUT_SECS });
+  });
+
+  it("rejects permissionless creation when the mint has no bond", async () => {
+    try {
+      await createNativeMarket({ creator });
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("PermissionlessCreationDisabled");
+    }
+  });
+
+  it("lets any wallet create a market by posting the bond", async () => {
+    await setCreatorBond(CREATOR_BOND);
+    const market = await createNativeMarket({ creator });
+
+    const m = await program.account.market.fetch(market);
+    expect(m.creator.equals(creator.publicKey)).to.be.true;
+    expect(m.creatorBond.toNumber()).to.equal(CREATOR_BOND);
+    expect(await connection.getBalance(vaultAuthorityPda(program.programId, market))).to.equal(
+      CREATOR_BOND
+    );
+  });
+
+  it("slashes the bond of a cancelled spam market to the treasury", async () => {
+    const market = await createNativeMarket({ creator });
+
+    try {
+      await release(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("CreatorBondNotReleasable");
+    }
+
+    // The market has to be cancelled before its bond can be slashed
+    try {
+      await slash(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("CreatorBondNotSlashable");
+    }
+
+    await program.methods
+      .cancelMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    const treasuryBefore = await connection.getBalance(treasury);
+
+    await slash(market);
+
+    expect((await connection.getBalance(treasury)) - treasuryBefore).to.equal(CREATOR_BOND);
+    expect((await program.account.market.fetch(market)).creatorBond.toNumber()).to.equal(0);
+  });
+
+  it("returns the bond once a market ends without positions", async () => {
+    const market = await createNativeMarket({ creator, endOffsetSecs: 2 });
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+    await delay(4000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    const creatorBefore = await connection.getBalance(creator.publicKey);
+    await release(market);
+    expect((await connection.getBalance(creator.publicKey)) - creatorBefore).to.equal(CREATOR_BOND);
+
+    try {
+      await release(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("NoCreatorBond");
+    }
+  });
+
+  it("returns the bond of a cancelled market after the emergency timeout", async () => {
+    await updateProtocol({ emergencyTimeoutSecs: EMERGENCY_TIMEOUT_SECS });
+    const market = await createNativeMarket({ creator, endOffsetSecs: 2 });
+    const vault = vaultAuthorityPda(program.programId, market);
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+    const position = positionPda(program.programId, market, user.publicKey, 0);
+    await program.methods
+      .placePositionNative(0, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .cancelMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    // Within the timeout the bond of a cancelled market can only be slashed
+    try {
+      await release(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("CreatorBondNotReleasable");
+    }
+
+    await delay((2 + EMERGENCY_TIMEOUT_SECS + 1) * 1000);
+
+    // Past the timeout the bond belongs to the creator and can no longer be slashed
+    try {
+      await slash(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("CreatorBondNotSlashable");
+    }
+
+    const creatorBefore = await connection.getBalance(creator.publicKey);
+    await release(market);
+    expect((await connection.getBalance(creator.publicKey)) - creatorBefore).to.equal(CREATOR_BOND);
+
+    // Once every position is refunded and closed the market can be closed
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    const closeMarket = () =>
+      program.methods
+        .closeEmptyMarketNative()
+        .accounts(accounts({
+          marketOperator: admin.publicKey,
+          protocol,
+          market,
+          marketItems: marketItemsPda(program.programId, market),
+          creator: creator.publicKey,
+          vault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .rpc();
+
+    try {
+      await closeMarket();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MarketNotEmpty");
+    }
+
+    await program.methods
+      .refundPositionNative()
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position,
+        vault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .signers([user])
+      .rpc();
+
+    // Refunded positions have to be closed before the market
+    try {
+      await closeMarket();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("OpenPositions");
+    }
+
+    await program.methods
+      .closePosition()
+      .accounts(accounts({ signer: admin.publicKey, market, position, user: user.publicKey }))
+      .rpc();
+    await closeMarket();
+
+    expect(await connection.getAccountInfo(market)).to.be.null;
+    expect(await connection.getAccountInfo(marketItemsPda(program.programId, market))).to.be.null;
+  });
+
+  it("returns the bond of a market that was never opened after the emergency timeout", async () => {
+    await updateProtocol({ emergencyTimeoutSecs: EMERGENCY_TIMEOUT_SECS });
+    const market = await createNativeMarket({ creator, endOffsetSecs: 2 });
+
+    try {
+      await release(market);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("CreatorBondNotReleasable");
+    }
+
+    await delay((2 + EMERGENCY_TIMEOUT_SECS + 1) * 1000);
+    const creatorBefore = await connection.getBalance(creator.publicKey);
+    await release(market);
+
+    expect((await connection.getBalance(creator.publicKey)) - creatorBefore).to.equal(CREATOR_BOND);
+    const m = await program.account.market.fetch(market);
+    expect(m.status.draft !== undefined).to.be.true;
+    expect(m.creatorBond.toNumber()).to.equal(0);
+  });
+});
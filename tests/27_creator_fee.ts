@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  BPS_MAX,
+  connection,
+  createNativeMarket,
+  delay,
+  ensureMintConfig,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  U64_MAX,
+  updateProtocol,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const MAX_CREATOR_FEE_BPS = 5_000;
+const CREATOR_FEE_BPS = 2_500;
+const CREATOR_BOND = 10_000_000;
+const RAW_STAKE = 10_000_000;
+
+describe("creator fee", () => {
+  let protocol: anchor.web3.PublicKey;
+  let mintConfig: anchor.web3.PublicKey;
+  let creator: anchor.web3.Keypair;
+
+  const fund = async (to: anchor.web3.PublicKey, lamports: number) =>
+    program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: to, lamports })
+      )
+    );
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    mintConfig = mintConfigPda(program.programId, NATIVE_STAKE_MINT);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+    await program.methods
+      .updateMintConfig(true, new anchor.BN(1), U64_MAX, new anchor.BN(CREATOR_BOND))
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol, mintConfig }))
+      .rpc();
+    await updateProtocol({ maxCreatorFeeBps: MAX_CREATOR_FEE_BPS });
+
+    creator = anchor.web3.Keypair.generate();
+    await fund(creator.publicKey, 500_000_000);
+  });
+
+  after(async () => {
+    await updateProtocol({ maxCreatorFeeBps: 0 });
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+  });
+
+  it("rejects a cap above 100%", async () => {
+    try {
+      await updateProtocol({ maxCreatorFeeBps: BPS_MAX + 1 });
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidCreatorFeeBps");
+    }
+  });
+
+  it("rejects a creator fee above the protocol maximum", async () => {
+    try {
+      await createNativeMarket({ creator, creatorFeeBps: MAX_CREATOR_FEE_BPS + 1 });
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidCreatorFeeBps");
+    }
+  });
+
+  it("splits the settlement fee between treasury and creator", async () => {
+    const market = await createNativeMarket({
+      creator,
+      creatorFeeBps: CREATOR_FEE_BPS,
+      endOffsetSecs: 4,
+    });
+    const vault = vaultAuthorityPda(program.programId, market);
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    for (const item of [0, 1]) {
+      const user = anchor.web3.Keypair.generate();
+      await fund(user.publicKey, 100_000_000);
+      await program.methods
+        .placePositionNative(item, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+        .accounts(accounts({
+          user: user.publicKey,
+          protocol,
+          market,
+          position: positionPda(program.programId, market, user.publicKey, item),
+          vault,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          referrerAccount: null,
+          marketReferral: null,
+        }))
+        .signers([user])
+        .rpc();
+    }
+
+    await delay(6000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .resolveMarket(0)
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    const { feeBps } = await program.account.market.fetch(market);
+    const treasuryBefore = await connection.getBalance(treasury);
+    const creatorBefore = await connection.getBalance(creator.publicKey);
+
+    await program.methods
+      .settleMarketNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vault,
+        treasury,
+        creator: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    const fee = Math.floor((2 * RAW_STAKE * feeBps) / BPS_MAX);
+    const creatorFee = Math.floor((fee * CREATOR_FEE_BPS) / BPS_MAX);
+
+    const m = await program.account.market.fetch(market);
+    expect(m.creatorFeeAmount.toNumber()).to.equal(creatorFee);
+    expect(m.protocolFeeAmount.toNumber()).to.equal(fee - creatorFee);
+    expect(m.distributablePool.toNumber()).to.equal(2 * RAW_STAKE - fee);
+    expect((await connection.getBalance(creator.publicKey)) - creatorBefore).to.equal(creatorFee);
+    expect((await connection.getBalance(treasury)) - treasuryBefore).to.equal(fee - creatorFee);
+  });
+});
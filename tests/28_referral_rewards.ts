@@ -0,0 +1,444 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  BPS_MAX,
+  connection,
+  createTransferFeeMint,
+  delay,
+  ensureMintConfig,
+  fundedUser,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  marketReferralPda,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  referrerPda,
+  TWO_ITEMS,
+  updateProtocol,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const REFERRAL_FEE_BPS = 5_000;
+const RAW_STAKE = 10_000_000;
+
+describe("referral rewards", () => {
+  let protocol: anchor.web3.PublicKey;
+  let market: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let referrer: anchor.web3.Keypair;
+  let referrerAccount: anchor.web3.PublicKey;
+  let referred: anchor.web3.Keypair;
+  let other: anchor.web3.Keypair;
+
+  const fund = async (to: anchor.web3.PublicKey, lamports: number) =>
+    program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: to, lamports })
+      )
+    );
+
+  const placePosition = (
+    user: anchor.web3.Keypair,
+    item: number,
+    referrerAcct: anchor.web3.PublicKey | null
+  ) =>
+    program.methods
+      .placePositionNative(item, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, item),
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        referrerAccount: referrerAcct,
+        marketReferral: referrerAcct && marketReferralPda(program.programId, market, referrerAcct),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .signers([user])
+      .rpc();
+
+  const collect = (payer: anchor.web3.PublicKey) =>
+    program.methods
+      .collectReferralRewardNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        marketReferral: marketReferralPda(program.programId, market, referrerAccount),
+        referrerAccount,
+        vault,
+        payer,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+    await updateProtocol({ referralFeeBps: REFERRAL_FEE_BPS });
+
+    referrer = anchor.web3.Keypair.generate();
+    referred = anchor.web3.Keypair.generate();
+    other = anchor.web3.Keypair.generate();
+    for (const k of [referrer, referred, other]) await fund(k.publicKey, 100_000_000);
+
+    referrerAccount = referrerPda(program.programId, referrer.publicKey, NATIVE_STAKE_MINT);
+    await program.methods
+      .createReferrerAccountNative()
+      .accounts(accounts({
+        referrer: referrer.publicKey,
+        referrerAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .signers([referrer])
+      .rpc();
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    market = marketPda(program.programId, marketCount);
+    vault = vaultAuthorityPda(program.programId, market);
+
+    await program.methods
+      .createMarketNative(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 4),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority: vault,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+  });
+
+  after(async () => await updateProtocol({ referralFeeBps: 0 }));
+
+  it("rejects self-referral", async () => {
+    try {
+      await placePosition(referrer, 0, referrerAccount);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("InvalidReferrer");
+    }
+  });
+
+  it("records the referrer on the position and its stake on the market referral", async () => {
+    await placePosition(referred, 0, referrerAccount);
+    await placePosition(other, 1, null);
+
+    const pos = await program.account.position.fetch(
+      positionPda(program.programId, market, referred.publicKey, 0)
+    );
+    expect(pos.referrer!.equals(referrer.publicKey)).to.be.true;
+    const m = await program.account.market.fetch(market);
+    expect(m.referredRawStake.toNumber()).to.equal(RAW_STAKE);
+    expect(m.referralCount.toNumber()).to.equal(1);
+
+    const referral = await program.account.marketReferral.fetch(
+      marketReferralPda(program.programId, market, referrerAccount)
+    );
+    expect(referral.referredRawStake.toNumber()).to.equal(RAW_STAKE);
+    expect(referral.payer.equals(referred.publicKey)).to.be.true;
+
+    // A top-up of a referred position must keep counting towards the same referral
+    try {
+      await placePosition(referred, 0, null);
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("MissingReferrerAccount");
+    }
+  });
+
+  it("pays the referral share even when the referred position loses", async () => {
+    await delay(6000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .resolveMarket(1)
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    const { feeBps } = await program.account.market.fetch(market);
+    await program.methods
+      .settleMarketNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vault,
+        treasury,
+        creator: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    const fee = Math.floor((2 * RAW_STAKE * feeBps) / BPS_MAX);
+    const reserve = Math.floor((Math.floor(fee / 2) * REFERRAL_FEE_BPS) / BPS_MAX);
+    const m = await program.account.market.fetch(market);
+    expect(m.referralReserve.toNumber()).to.equal(reserve);
+    expect(m.protocolFeeAmount.toNumber()).to.equal(fee - reserve);
+
+    // The referred position lost and never claims; anyone can collect for the referrer
+    const referral = marketReferralPda(program.programId, market, referrerAccount);
+    const rent = await connection.getBalance(referral);
+    const payerBefore = await connection.getBalance(referred.publicKey);
+    await collect(referred.publicKey);
+
+    const ra = await program.account.referrerAccount.fetch(referrerAccount);
+    expect(ra.accrued.toNumber()).to.equal(reserve);
+    expect(ra.totalEarned.toNumber()).to.equal(reserve);
+    expect(await connection.getAccountInfo(referral)).to.be.null;
+    expect((await connection.getBalance(referred.publicKey)) - payerBefore).to.equal(rent);
+    expect((await program.account.market.fetch(market)).referralsCollected.toNumber()).to.equal(1);
+  });
+
+  it("lets the referrer withdraw accrued rewards", async () => {
+    const { accrued } = await program.account.referrerAccount.fetch(referrerAccount);
+    const before = await connection.getBalance(referrer.publicKey);
+
+    const withdraw = () =>
+      program.methods
+        .withdrawReferralRewardsNative()
+        .accounts(accounts({
+          referrer: referrer.publicKey,
+          protocol,
+          referrerAccount,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }))
+        .signers([referrer])
+        .rpc();
+
+    await withdraw();
+    expect((await connection.getBalance(referrer.publicKey)) - before).to.equal(accrued.toNumber());
+
+    try {
+      await withdraw();
+      expect.fail("should have thrown");
+    } catch (e: unknown) {
+      const err = e as { message?: string };
+      expect(err.message || err).to.include("NoReferralRewards");
+    }
+  });
+
+  it("credits the reward that reaches a transfer-fee referrer vault", async () => {
+    const tokenMint = await createTransferFeeMint();
+    await ensureMintConfig(tokenMint);
+    const mintConfig = mintConfigPda(program.programId, tokenMint);
+    const ata = (owner: anchor.web3.PublicKey) =>
+      getAssociatedTokenAddressSync(
+        tokenMint,
+        owner,
+        true,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+    const tokenAccounts = {
+      tokenMint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    const splReferrerAccount = referrerPda(program.programId, referrer.publicKey, tokenMint);
+    const referrerVault = ata(splReferrerAccount);
+    await program.methods
+      .createReferrerAccount()
+      .accounts(accounts({
+        referrer: referrer.publicKey,
+        referrerAccount: splReferrerAccount,
+        referrerVault,
+        ...tokenAccounts,
+      }))
+      .signers([referrer])
+      .rpc();
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    const splMarket = marketPda(program.programId, marketCount);
+    const vaultAuthority = vaultAuthorityPda(program.programId, splMarket);
+    const splVault = ata(vaultAuthority);
+
+    await program.methods
+      .createMarket(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 4),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market: splMarket,
+        marketItems: marketItemsPda(program.programId, splMarket),
+        vaultAuthority,
+        vault: splVault,
+        mintConfig,
+        creatorTokenAccount: null,
+        ...tokenAccounts,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market: splMarket }))
+      .rpc();
+
+    const stakers = [
+      { ...(await fundedUser(tokenMint)), item: 0, referrerAccount: splReferrerAccount },
+      { ...(await fundedUser(tokenMint)), item: 1, referrerAccount: null },
+    ];
+    for (const s of stakers) {
+      await program.methods
+        .placePosition(s.item, new anchor.BN(RAW_STAKE), new anchor.BN(0))
+        .accounts(accounts({
+          user: s.user.publicKey,
+          protocol,
+          market: splMarket,
+          position: positionPda(program.programId, splMarket, s.user.publicKey, s.item),
+          userTokenAccount: s.ata,
+          vault: splVault,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          attestationReceipt: null,
+          mintConfig,
+          referrerAccount: s.referrerAccount,
+          marketReferral:
+            s.referrerAccount && marketReferralPda(program.programId, splMarket, s.referrerAccount),
+          ...tokenAccounts,
+        }))
+        .signers([s.user])
+        .rpc();
+    }
+
+    await delay(6000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market: splMarket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .resolveMarket(0)
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market: splMarket }))
+      .rpc();
+
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    await program.methods
+      .settleMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market: splMarket,
+        vaultAuthority,
+        vault: splVault,
+        treasury,
+        treasuryTokenAccount: ata(treasury),
+        creator: admin.publicKey,
+        creatorTokenAccount: ata(admin.publicKey),
+        ...tokenAccounts,
+      }))
+      .rpc();
+
+    const [referredStaker] = stakers;
+    await program.methods
+      .collectReferralReward()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market: splMarket,
+        marketReferral: marketReferralPda(program.programId, splMarket, splReferrerAccount),
+        referrerAccount: splReferrerAccount,
+        referrerVault,
+        vaultAuthority,
+        vault: splVault,
+        payer: referredStaker.user.publicKey,
+        ...tokenAccounts,
+      }))
+      .rpc();
+
+    // The vault-side reward is the full reserve; the referrer is credited what arrived
+    const { referralReserve } = await program.account.market.fetch(splMarket);
+    const vaultBalance = Number(
+      (await getAccount(connection, referrerVault, undefined, TOKEN_2022_PROGRAM_ID)).amount
+    );
+    const ra = await program.account.referrerAccount.fetch(splReferrerAccount);
+    expect(vaultBalance).to.be.lessThan(referralReserve.toNumber());
+    expect(ra.accrued.toNumber()).to.equal(vaultBalance);
+
+    const referrerTokenAccount = await createAssociatedTokenAccountIdempotent(
+      connection,
+      admin.payer,
+      tokenMint,
+      referrer.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await program.methods
+      .withdrawReferralRewards()
+      .accounts(accounts({
+        referrer: referrer.publicKey,
+        protocol,
+        referrerAccount: splReferrerAccount,
+        referrerVault,
+        referrerTokenAccount,
+        tokenMint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }))
+      .signers([referrer])
+      .rpc();
+
+    expect((await program.account.referrerAccount.fetch(splReferrerAccount)).accrued.toNumber())
+      .to.equal(0);
+    expect(
+      Number((await getAccount(connection, referrerVault, undefined, TOKEN_2022_PROGRAM_ID)).amount)
+    ).to.equal(0);
+  });
+});
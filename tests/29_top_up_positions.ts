@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  accounts,
+  admin,
+  connection,
+  cpiEvents,
+  delay,
+  ensureMintConfig,
+  FLAT_SCHEDULE,
+  itemsHash,
+  marketItemsPda,
+  marketPda,
+  mintConfigPda,
+  NATIVE_STAKE_MINT,
+  positionPda,
+  program,
+  protocolPda,
+  TWO_ITEMS,
+  vaultAuthorityPda,
+  SUITE_DELAY_MS,
+} from "./helpers";
+
+const RAW_STAKE = 10_000_000;
+
+describe("top-up positions", () => {
+  let protocol: anchor.web3.PublicKey;
+  let market: anchor.web3.PublicKey;
+  let vault: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+
+  const placePosition = (item: number, rawStake: number) =>
+    program.methods
+      .placePositionNative(item, new anchor.BN(rawStake), new anchor.BN(0))
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, item),
+        vault,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        attestationReceipt: null,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        referrerAccount: null,
+        marketReferral: null,
+      }))
+      .signers([user])
+      .rpc();
+
+  const claim = (item: number) =>
+    program.methods
+      .claimPayoutNative(false)
+      .accounts(accounts({
+        user: user.publicKey,
+        protocol,
+        market,
+        position: positionPda(program.programId, market, user.publicKey, item),
+        vault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await delay(SUITE_DELAY_MS);
+    protocol = protocolPda(program.programId);
+    await ensureMintConfig(NATIVE_STAKE_MINT);
+
+    const slot = await connection.getSlot();
+    const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+    const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+    market = marketPda(program.programId, marketCount);
+    vault = vaultAuthorityPda(program.programId, market);
+
+    await program.methods
+      .createMarketNative(
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 8),
+        itemsHash(TWO_ITEMS),
+        2,
+        TWO_ITEMS,
+        FLAT_SCHEDULE,
+        0
+      )
+      .accounts(accounts({
+        creator: admin.publicKey,
+        protocol,
+        market,
+        marketItems: marketItemsPda(program.programId, market),
+        vaultAuthority: vault,
+        mintConfig: mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .openMarket()
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: user.publicKey,
+          lamports: 500_000_000,
+        })
+      )
+    );
+  });
+
+  it("tops up the position held on the same item", async () => {
+    await placePosition(0, RAW_STAKE);
+    const signature = await placePosition(0, 2 * RAW_STAKE);
+
+    const pos = await program.account.position.fetch(
+      positionPda(program.programId, market, user.publicKey, 0)
+    );
+    expect(pos.rawStake.toNumber()).to.equal(3 * RAW_STAKE);
+    expect(pos.effectiveStake.toNumber()).to.equal(3 * RAW_STAKE);
+
+    const m = await program.account.market.fetch(market);
+    expect(m.positionCount.toNumber()).to.equal(1);
+    expect(m.totalRawStake.toNumber()).to.equal(3 * RAW_STAKE);
+
+    const [placed] = (await cpiEvents(signature)).filter((e) => e.name === "positionPlaced");
+    expect(placed.data.topUp).to.be.true;
+    expect(placed.data.rawStake.toNumber()).to.equal(2 * RAW_STAKE);
+    expect(placed.data.positionRawStake.toNumber()).to.equal(3 * RAW_STAKE);
+  });
+
+  it("opens a separate position on another item", async () => {
+    await placePosition(1, RAW_STAKE);
+
+    const pos = await program.account.position.fetch(
+      positionPda(program.programId, market, user.publicKey, 1)
+    );
+    expect(pos.selectedItemIndex).to.equal(1);
+    expect(pos.rawStake.toNumber()).to.equal(RAW_STAKE);
+
+    const m = await program.account.market.fetch(market);
+    expect(m.positionCount.toNumber()).to.equal(2);
+    expect(m.totalRawStake.toNumber()).to.equal(4 * RAW_STAKE);
+  });
+
+  it("claims each of the user's positions", async () => {
+    await delay(8000);
+    await program.methods
+      .closeMarket()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+    await program.methods
+      .resolveMarket(0)
+      .accounts(accounts({ marketOperator: admin.publicKey, protocol, market }))
+      .rpc();
+
+    const { treasury } = await program.account.protocol.fetch(protocol);
+    await program.methods
+      .settleMarketNative()
+      .accounts(accounts({
+        signer: admin.publicKey,
+        protocol,
+        market,
+        vault,
+        treasury,
+        creator: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+
+    const { distributablePool } = await program.account.market.fetch(market);
+    const before = await connection.getBalance(user.publicKey);
+    await claim(0);
+    await claim(1);
+
+    for (const item of [0, 1]) {
+      const pos = await program.account.position.fetch(
+        positionPda(program.programId, market, user.publicKey, item)
+      );
+      expect(pos.claimed).to.be.true;
+    }
+    // The user is the only staker on the winning item, so the whole pool goes to them
+    expect((await connection.getBalance(user.publicKey)) - before).to.equal(
+      distributablePool.toNumber()
+    );
+  });
+});
@@ -6,6 +6,17 @@
  */
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccountIdempotent,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  ExtensionType,
+  getMintLen,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { blake3 } from "@noble/hashes/blake3";
+import { createHash } from "crypto";
 import { KleosProtocol } from "../target/types/kleos_protocol";
 
 anchor.setProvider(anchor.AnchorProvider.env());
@@ -17,12 +28,44 @@ export const connection = program.provider.connection;
 export const BPS_MAX = 10_000;
 export const MAX_MULTIPLIER = 20;
 export const ZERO_ITEMS_HASH = new Array(32).fill(0) as number[];
+export const TWO_ITEMS = ["Yes", "No"];
+/** Multiplier schedules in bps of raw stake (10_000 = 1x). */
+export const FLAT_SCHEDULE = { linear: { startMultiplierBps: 10_000, endMultiplierBps: 10_000 } };
+export const EARLY_BIRD_SCHEDULE = { linear: { startMultiplierBps: 20_000, endMultiplierBps: 10_000 } };
+/** Effective stake supplied per position by a scoring-authority attestation. */
+export const ATTESTED_SCHEDULE = { attested: {} };
+
+/** Scoring authority registered on the protocol by the update_protocol suites. */
+export const scoringAuthority = anchor.web3.Keypair.generate();
+
+/** `Protocol.pause_flags` bits; mirror `constants.rs`. */
+export const PAUSE_MARKET_CREATION = 1 << 0;
+export const PAUSE_DEPOSITS = 1 << 1;
+export const PAUSE_SETTLEMENT = 1 << 2;
+export const PAUSE_CLAIMS = 1 << 3;
+export const PAUSE_ALL = PAUSE_MARKET_CREATION | PAUSE_DEPOSITS | PAUSE_SETTLEMENT | PAUSE_CLAIMS;
 
 export const SUITE_DELAY_MS = 1500;
 export const delay = (ms: number) => new Promise<void>((r) => setTimeout(r, ms));
 
 export const accounts = (x: object): any => x;
 
+function u32le(n: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(n);
+  return buf;
+}
+
+/** Canonical items hash: blake3 over the Borsh encoding of the label list (matches MarketItems::hash_labels). */
+export function itemsHash(labels: string[]): number[] {
+  const parts: Buffer[] = [u32le(labels.length)];
+  for (const label of labels) {
+    const bytes = Buffer.from(label, "utf8");
+    parts.push(u32le(bytes.length), bytes);
+  }
+  return Array.from(blake3(Buffer.concat(parts)));
+}
+
 export function protocolPda(programId: anchor.web3.PublicKey) {
   return anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("protocol")],
@@ -52,10 +95,314 @@ export function vaultAuthorityPda(
 export function positionPda(
   programId: anchor.web3.PublicKey,
   market: anchor.web3.PublicKey,
-  user: anchor.web3.PublicKey
+  user: anchor.web3.PublicKey,
+  itemIndex: number
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("position"), market.toBuffer(), user.toBuffer(), Buffer.from([itemIndex])],
+    programId
+  )[0];
+}
+
+export function marketItemsPda(
+  programId: anchor.web3.PublicKey,
+  market: anchor.web3.PublicKey
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("market_items"), market.toBuffer()],
+    programId
+  )[0];
+}
+
+/** Message signed by the scoring authority (matches attestation::ATTESTATION_MESSAGE_LEN layout). */
+export function attestationMessage(
+  market: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey,
+  itemIndex: number,
+  rawStake: number,
+  effectiveStake: number,
+  expiryTs: number
+): Buffer {
+  const buf = Buffer.alloc(97);
+  market.toBuffer().copy(buf, 0);
+  user.toBuffer().copy(buf, 32);
+  buf.writeUInt8(itemIndex, 64);
+  buf.writeBigUInt64LE(BigInt(rawStake), 65);
+  buf.writeBigUInt64LE(BigInt(effectiveStake), 73);
+  buf.writeBigUInt64LE(BigInt(0), 81);
+  buf.writeBigInt64LE(BigInt(expiryTs), 89);
+  return buf;
+}
+
+/** Ed25519 program instruction carrying a scoring-authority signature over `message`. */
+export function attestationIx(message: Buffer): anchor.web3.TransactionInstruction {
+  return anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: scoringAuthority.secretKey,
+    message,
+  });
+}
+
+export function attestationReceiptPda(
+  programId: anchor.web3.PublicKey,
+  message: Buffer
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("attestation"), createHash("sha256").update(message).digest()],
+    programId
+  )[0];
+}
+
+/** Decode the `emit_cpi!` events recorded as inner instructions of a confirmed transaction. */
+export async function cpiEvents(signature: string): Promise<{ name: string; data: any }[]> {
+  await connection.confirmTransaction(signature, "confirmed");
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const keys = tx?.transaction.message.getAccountKeys();
+  const events: { name: string; data: any }[] = [];
+  for (const inner of tx?.meta?.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      if (!keys?.get(ix.programIdIndex)?.equals(program.programId)) continue;
+      // Strip the 8-byte event-CPI tag; the rest is the event discriminator + payload
+      const data = anchor.utils.bytes.bs58.decode(ix.data).subarray(8);
+      const event = program.coder.events.decode(anchor.utils.bytes.base64.encode(Buffer.from(data)));
+      if (event) events.push(event);
+    }
+  }
+  return events;
+}
+
+/** Mint key used by native SOL markets (and their mint config). */
+export const NATIVE_STAKE_MINT = anchor.web3.SystemProgram.programId;
+/** Emergency timeout the protocol is initialised with; suites that shorten it restore this. */
+export const DEFAULT_EMERGENCY_TIMEOUT_SECS = 30 * 24 * 60 * 60;
+/** Claim window the protocol is initialised with; suites that shorten it restore this. */
+export const DEFAULT_CLAIM_WINDOW_SECS = 90 * 24 * 60 * 60;
+export const U64_MAX = new anchor.BN("18446744073709551615");
+
+export function mintConfigPda(
+  programId: anchor.web3.PublicKey,
+  mint: anchor.web3.PublicKey
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("mint_config"), mint.toBuffer()],
+    programId
+  )[0];
+}
+
+export function referrerPda(
+  programId: anchor.web3.PublicKey,
+  referrer: anchor.web3.PublicKey,
+  mint: anchor.web3.PublicKey
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("referrer"), referrer.toBuffer(), mint.toBuffer()],
+    programId
+  )[0];
+}
+
+export function marketReferralPda(
+  programId: anchor.web3.PublicKey,
+  market: anchor.web3.PublicKey,
+  referrerAccount: anchor.web3.PublicKey
 ) {
   return anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("position"), market.toBuffer(), user.toBuffer()],
+    [Buffer.from("referral"), market.toBuffer(), referrerAccount.toBuffer()],
     programId
   )[0];
 }
+
+/** Approve `mint` for markets (min stake 1, no upper bound) unless it already is. */
+export async function ensureMintConfig(mint: anchor.web3.PublicKey) {
+  const protocol = protocolPda(program.programId);
+  const mintConfig = mintConfigPda(program.programId, mint);
+  const existing = await program.account.mintConfig.fetchNullable(mintConfig);
+
+  if (existing === null) {
+    await program.methods
+      .createMintConfig(mint, new anchor.BN(1), U64_MAX, new anchor.BN(0))
+      .accounts(accounts({
+        adminAuthority: admin.publicKey,
+        protocol,
+        mintConfig,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }))
+      .rpc();
+  } else if (
+    !existing.enabled ||
+    !existing.minStake.eqn(1) ||
+    !existing.maxStake.eq(U64_MAX) ||
+    !existing.creatorBond.isZero()
+  ) {
+    await program.methods
+      .updateMintConfig(true, new anchor.BN(1), U64_MAX, new anchor.BN(0))
+      .accounts(accounts({ adminAuthority: admin.publicKey, protocol, mintConfig }))
+      .rpc();
+  }
+}
+
+export type ProtocolUpdate = {
+  protocolFeeBps?: number;
+  maxCreatorFeeBps?: number;
+  referralFeeBps?: number;
+  treasury?: anchor.web3.PublicKey;
+  scoringAuthority?: anchor.web3.PublicKey;
+  timelockDelaySecs?: number;
+  emergencyTimeoutSecs?: number;
+  claimWindowSecs?: number;
+};
+
+/** Queue a protocol update signed by the test wallet and execute it. The test protocol runs
+ * with a zero timelock delay, so the update is executable immediately. */
+export async function updateProtocol(update: ProtocolUpdate) {
+  const protocol = protocolPda(program.programId);
+  await program.methods
+    .queueProtocolUpdate(
+      update.protocolFeeBps ?? null,
+      update.maxCreatorFeeBps ?? null,
+      update.referralFeeBps ?? null,
+      update.treasury ?? null,
+      update.scoringAuthority ?? null,
+      update.timelockDelaySecs === undefined ? null : new anchor.BN(update.timelockDelaySecs),
+      update.emergencyTimeoutSecs === undefined ? null : new anchor.BN(update.emergencyTimeoutSecs),
+      update.claimWindowSecs === undefined ? null : new anchor.BN(update.claimWindowSecs)
+    )
+    .accounts(accounts({ authority: admin.publicKey, protocol }))
+    .rpc();
+  await program.methods
+    .executeProtocolUpdate()
+    .accounts(accounts({ protocol }))
+    .rpc();
+}
+
+export type NativeMarketOptions = {
+  /** Signs and pays for the market; the test wallet if omitted. */
+  creator?: anchor.web3.Keypair;
+  endOffsetSecs?: number;
+  creatorFeeBps?: number;
+  mintConfig?: anchor.web3.PublicKey;
+};
+
+/** Create the next native-SOL Draft market: two items, a flat schedule, started a minute ago
+ * and ending `endOffsetSecs` (default an hour) from now. Returns the market address. */
+export async function createNativeMarket({
+  creator,
+  endOffsetSecs = 3600,
+  creatorFeeBps = 0,
+  mintConfig = mintConfigPda(program.programId, NATIVE_STAKE_MINT),
+}: NativeMarketOptions = {}) {
+  const protocol = protocolPda(program.programId);
+  const slot = await connection.getSlot();
+  const now = (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+  const marketCount = (await program.account.protocol.fetch(protocol)).marketCount.toNumber();
+  const market = marketPda(program.programId, marketCount);
+
+  await program.methods
+    .createMarketNative(
+      new anchor.BN(now - 60),
+      new anchor.BN(now + endOffsetSecs),
+      itemsHash(TWO_ITEMS),
+      2,
+      TWO_ITEMS,
+      FLAT_SCHEDULE,
+      creatorFeeBps
+    )
+    .accounts(accounts({
+      creator: creator?.publicKey ?? admin.publicKey,
+      protocol,
+      market,
+      marketItems: marketItemsPda(program.programId, market),
+      vaultAuthority: vaultAuthorityPda(program.programId, market),
+      mintConfig,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }))
+    .signers(creator ? [creator] : [])
+    .rpc();
+
+  return market;
+}
+
+export const TOKEN_2022_DECIMALS = 6;
+/** Transfer fee withheld by `createTransferFeeMint` mints. */
+export const TRANSFER_FEE_BPS = 100; // 1%
+
+/** Create a Token-2022 mint with the given extensions initialised by `extensionIxs`. */
+export async function createToken2022Mint(
+  extensions: ExtensionType[],
+  extensionIxs: (mint: anchor.web3.PublicKey) => anchor.web3.TransactionInstruction[]
+): Promise<anchor.web3.PublicKey> {
+  const mint = anchor.web3.Keypair.generate();
+  const space = getMintLen(extensions);
+  const lamports = await connection.getMinimumBalanceForRentExemption(space);
+
+  await program.provider.sendAndConfirm(
+    new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      ...extensionIxs(mint.publicKey),
+      createInitializeMintInstruction(
+        mint.publicKey,
+        TOKEN_2022_DECIMALS,
+        admin.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    ),
+    [mint]
+  );
+
+  return mint.publicKey;
+}
+
+/** Token-2022 mint that withholds `TRANSFER_FEE_BPS` of every transfer. */
+export const createTransferFeeMint = () =>
+  createToken2022Mint([ExtensionType.TransferFeeConfig], (mint) => [
+    createInitializeTransferFeeConfigInstruction(
+      mint,
+      admin.publicKey,
+      admin.publicKey,
+      TRANSFER_FEE_BPS,
+      BigInt(1_000_000_000),
+      TOKEN_2022_PROGRAM_ID
+    ),
+  ]);
+
+/** Fund a fresh wallet with SOL and `tokenMint` tokens. */
+export async function fundedUser(tokenMint: anchor.web3.PublicKey) {
+  const user = anchor.web3.Keypair.generate();
+  await program.provider.sendAndConfirm(
+    new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: admin.publicKey,
+        toPubkey: user.publicKey,
+        lamports: 100_000_000,
+      })
+    )
+  );
+  const ata = await createAssociatedTokenAccountIdempotent(
+    connection,
+    admin.payer,
+    tokenMint,
+    user.publicKey,
+    undefined,
+    TOKEN_2022_PROGRAM_ID
+  );
+  await mintTo(
+    connection,
+    admin.payer,
+    tokenMint,
+    ata,
+    admin.publicKey,
+    1e9,
+    [],
+    undefined,
+    TOKEN_2022_PROGRAM_ID
+  );
+  return { user, ata };
+}